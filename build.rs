@@ -0,0 +1,76 @@
+//! Generates the solution registry that is compiled into the `all` binary.
+//! Every scaffolded `src/bin/NN.rs` and `src/bin/YYYY_NN.rs` is included as a module, so all days can
+//! run in a single process.
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let stem = name.strip_suffix(".rs")?;
+                    is_solution(stem).then(|| stem.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort_unstable();
+
+    // every solution declares its own global allocator when profiling, they can't share a binary.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some() {
+        days.clear();
+    }
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        writeln!(modules, "    #[cfg(not(test))]").unwrap();
+        writeln!(modules, "    #[allow(dead_code)]").unwrap();
+        writeln!(modules, "    #[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(modules, "    mod day_{day};").unwrap();
+        writeln!(entries, "            day_{day}::__solution(),").unwrap();
+    }
+
+    let registry = format!(
+        "/// Solutions compiled into this binary.
+mod solutions {{
+    use advent_of_code::template::registry::Solution;
+
+{modules}
+    #[cfg(not(test))]
+    pub fn all() -> Vec<Solution> {{
+        vec![
+{entries}        ]
+    }}
+
+    /// Tests of each day already run in their own binary.
+    #[cfg(test)]
+    pub fn all() -> Vec<Solution> {{
+        vec![]
+    }}
+}}
+"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
+
+/// Matches the bin names of both layouts, `NN` and `YYYY_NN`.
+fn is_solution(stem: &str) -> bool {
+    let is_day =
+        |day: &str| day.len() == 2 && day.parse::<u8>().is_ok_and(|d| (1..=25).contains(&d));
+
+    match stem.split_once('_') {
+        Some((year, day)) => year.len() == 4 && year.parse::<u16>().is_ok() && is_day(day),
+        None => is_day(stem),
+    }
+}
@@ -7,7 +7,9 @@ use nom::{
     IResult,
 };
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
+
+use advent_of_code::graph::{topo_sort, Cycle};
 
 advent_of_code::solution!(5);
 
@@ -45,49 +47,15 @@ fn parse_data(input: &str) -> IResult<&str, Data> {
 }
 
 /// Function to reduce the ordering rules to a hiererchy chain A < B < C < D
-fn reduce_to_chain(rules: &Vec<(u32, u32)>) -> Vec<u32> {
+fn reduce_to_chain(rules: &[(u32, u32)]) -> Result<Vec<u32>, Cycle<u32>> {
     let mut graph: HashMap<u32, Vec<u32>> = HashMap::new(); // Graph structure, for each node contains all its connexions
-    let mut in_degree: HashMap<u32, u32> = HashMap::new(); // Counts how many inwards connexion for each node
-    let mut nodes: HashSet<u32> = HashSet::new(); // The list of nodes
 
-    // Build the graph and track in-degrees
     for &(a, b) in rules {
         graph.entry(a).or_default().push(b);
-        *in_degree.entry(b).or_default() += 1; // b just got a new inward connexion
-        in_degree.entry(a).or_default();
-        nodes.insert(a);
-        nodes.insert(b);
     }
 
-    // Find all nodes with zero in-degree to start
-    let mut queue: VecDeque<u32> = VecDeque::new();
-    for node in nodes.iter() {
-        if *in_degree.get(node).expect("Didn't find the node !") == 0 {
-            queue.push_back(*node);
-        }
-    }
-    // Perform topological sort
-    let mut result = Vec::new();
-    while let Some(node) = queue.pop_front() {
-        result.push(node);
-        if let Some(neighbors) = graph.get(&node) {
-            // Reduce the in-degree of each neighbor by 1 because one of its dependencies
-            // (the current node) has now been processed.
-            for &neighbor in neighbors {
-                let degree = in_degree.get_mut(&neighbor).unwrap();
-                *degree -= 1;
-                if *degree == 0 {
-                    // if the degree of the neighbor is zero, it means it has no more
-                    // inward conexion, so we'll deal with it next time !
-                    queue.push_back(neighbor);
-                }
-            }
-        }
-    }
-    if result.len() != nodes.len() {
-        panic!("The input rules contain a cycle and cannot be reduced to a chain.");
-    }
-    result
+    // Every node of the rules is a root candidate, the sort skips the ones it already reached
+    topo_sort(&graph, rules.iter().flat_map(|&(a, b)| [a, b]))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -123,7 +91,9 @@ pub fn part_two(input: &str) -> Option<u32> {
                 .filter(|rule| update.contains(&rule.0) && update.contains(&rule.1))
                 .collect_vec();
             // Reduce the graph rules to an hierarchy list
-            let chain = reduce_to_chain(&rules);
+            let chain = reduce_to_chain(&rules).unwrap_or_else(|cycle| {
+                panic!("The input rules cannot be reduced to a chain: {cycle}")
+            });
             // Sort the update using the chain
             let mut new_update = update.clone();
             new_update.sort_by(|a, b| {
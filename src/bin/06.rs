@@ -1,142 +1,190 @@
-use indicatif::ParallelProgressIterator;
-use itertools::{iproduct, Itertools};
+use advent_of_code::grid::{Grid, DIRECTIONS_4};
+use glam::IVec2;
 use rayon::prelude::*;
-use std::collections::HashSet;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse = parse_lab);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut map = input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec();
+pub struct Lab {
+    map: Grid<char>,
+    start: IVec2,
+}
+
+fn parse_lab(input: &str) -> Lab {
+    let map: Grid<char> = input.parse().unwrap();
 
     // Locate the ^
-    let (starting_pos, _) = map
-        .iter()
-        .flatten()
-        .find_position(|x| **x == '^')
+    let start = map
+        .find(|x| *x == '^')
         .expect("Did not find the the starting position :(");
 
-    // Convert to a (x, y) position
-    let width = map[0].len() as i32;
-    let height = map.len() as i32;
-    let starting_pos = ((starting_pos as i32 % width), (starting_pos as i32 / width));
+    Lab { map, start }
+}
 
-    let directions: [(i32, i32); 4] = [
-        (0, -1), // up
-        (1, 0),  // right
-        (0, 1),  // down
-        (-1, 0), // left
-    ];
+/// Every cell the guard walks over before leaving the map, in the order of the first visit.
+fn guard_path(lab: &Lab) -> Vec<IVec2> {
+    let mut visited = Grid::filled(lab.map.width(), lab.map.height(), false);
+    let mut path = Vec::new();
 
-    let mut current_pos = starting_pos;
+    // The directions are up, right, down, left
+    let mut current_pos = lab.start;
     let mut direction_switch = 0;
     loop {
-        // Mark the current with a X
-        map[current_pos.1 as usize][current_pos.0 as usize] = 'X';
+        if !visited[current_pos] {
+            visited[current_pos] = true;
+            path.push(current_pos);
+        }
 
         // Get the current direction
-        let current_dir = directions[direction_switch % 4];
-        // Update the current position
-        let new_pos = (current_pos.0 + current_dir.0, current_pos.1 + current_dir.1);
+        let current_dir = DIRECTIONS_4[direction_switch % 4];
+        let new_pos = current_pos + current_dir;
         // exit if we are out of the map
-        if new_pos.0 < 0 || new_pos.1 < 0 || new_pos.0 >= width || new_pos.1 >= height {
+        let Some(current_char) = lab.map.get(new_pos) else {
             break;
-        }
+        };
 
-        // Get the current char
-        let current_char = map[new_pos.1 as usize][new_pos.0 as usize];
         // Switch the direction if hit something
-        if current_char == '#' {
+        if *current_char == '#' {
             direction_switch += 1;
         } else {
-            // Update the current position
             current_pos = new_pos;
         }
     }
 
-    Some(map.iter().flatten().filter(|x| **x == 'X').count() as u32)
+    path
+}
+
+pub fn part_one(lab: &Lab) -> Option<u32> {
+    Some(guard_path(lab).len() as u32)
+}
+
+/// Where the guard ends up when walking straight from a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Jump {
+    /// The last cell before an obstacle, or before the edge of the map.
+    stop: IVec2,
+    /// Whether the guard walks off the map after `stop` instead of turning.
+    exits: bool,
+}
+
+/// Builds the jump of every cell in each of the four directions, so the guard can skip straight
+/// to the next turn instead of walking cell by cell.
+fn jump_table(map: &Grid<char>) -> Grid<[Jump; 4]> {
+    let no_jump = Jump {
+        stop: IVec2::ZERO,
+        exits: true,
+    };
+    let mut jumps = Grid::filled(map.width(), map.height(), [no_jump; 4]);
+    let cells = map.iter().map(|(pos, _)| pos).collect::<Vec<_>>();
+
+    for (d, dir) in DIRECTIONS_4.iter().enumerate() {
+        // The jump of a cell follows from the jump of the next cell in its direction, which has
+        // to be computed first: walking up or left, go through the grid from the top left.
+        let order: Box<dyn Iterator<Item = &IVec2>> = if dir.x < 0 || dir.y < 0 {
+            Box::new(cells.iter())
+        } else {
+            Box::new(cells.iter().rev())
+        };
+
+        for &pos in order {
+            let next = pos + *dir;
+            jumps[pos][d] = match map.get(next) {
+                None => Jump {
+                    stop: pos,
+                    exits: true,
+                },
+                Some('#') => Jump {
+                    stop: pos,
+                    exits: false,
+                },
+                Some(_) => jumps[next][d],
+            };
+        }
+    }
+
+    jumps
+}
+
+/// One bit per cell and direction, cheap to clear between candidates.
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        BitSet {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// Sets the bit, returns `false` if it was set before.
+    fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let is_new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        is_new
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
+}
+
+/// Walks the guard from turn to turn with an extra obstacle, and tells if it ends up in a loop.
+/// The guard is in a loop once it turns at the same cell in the same direction twice.
+fn is_loop(lab: &Lab, jumps: &Grid<[Jump; 4]>, obstacle: IVec2, visited: &mut BitSet) -> bool {
+    let mut pos = lab.start;
+    let mut d = 0;
+
+    loop {
+        let dir = DIRECTIONS_4[d];
+        let jump = jumps[pos][d];
+
+        // The table does not know the extra obstacle, stop in front of it if it is in the way
+        let steps = (jump.stop - pos).dot(dir);
+        let to_obstacle = (obstacle - pos).dot(dir);
+        let on_ray = pos + dir * to_obstacle == obstacle;
+
+        if on_ray && (1..=steps).contains(&to_obstacle) {
+            pos = obstacle - dir;
+        } else if jump.exits {
+            return false;
+        } else {
+            pos = jump.stop;
+        }
+
+        let index = lab.map.index_of(pos).unwrap() * 4 + d;
+        if !visited.insert(index) {
+            return true;
+        }
+
+        d = (d + 1) % 4;
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map: Vec<Vec<char>> = input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec();
-
-    // Locate the '^'
-    let (starting_pos, _) = map
-        .iter()
-        .flatten()
-        .find_position(|&&x| x == '^')
-        .expect("Did not find the starting position :(");
-
-    // Convert to a (x, y) position
-    let width = map[0].len() as i32;
-    let height = map.len() as i32;
-    let starting_pos = (starting_pos as i32 % width, starting_pos as i32 / width);
-    let directions: [(i32, i32); 4] = [
-        (0, -1), // up
-        (1, 0),  // right
-        (0, 1),  // down
-        (-1, 0), // left
-    ];
-
-    // Precompute valid crate positions
-    let crate_positions: Vec<_> = iproduct!(0..width, 0..height)
-        .filter(|&crate_pos| {
-            crate_pos != starting_pos && map[crate_pos.1 as usize][crate_pos.0 as usize] != '#'
-        })
+pub fn part_two(lab: &Lab) -> Option<u32> {
+    let jumps = jump_table(&lab.map);
+    let cells = lab.map.width() * lab.map.height();
+
+    // An obstacle off the path of the guard never changes it, only the path is worth trying
+    let candidates: Vec<IVec2> = guard_path(lab)
+        .into_iter()
+        .filter(|pos| *pos != lab.start)
         .collect();
 
-    let total_pos = crate_positions.len() as u64;
-    let loop_count = crate_positions
-        .into_par_iter() // Parallel iterator
-        .progress_count(total_pos)
-        .map(|crate_pos| {
-            // Clone the map for each thread
-            let mut local_map = map.clone();
-            // Add the crate to the map
-            local_map[crate_pos.1 as usize][crate_pos.0 as usize] = '#';
-
-            let mut current_pos = starting_pos;
-            let mut direction_switch = 0;
-            let mut visited: HashSet<((i32, i32), usize)> = HashSet::new();
-            let mut in_loop = 0;
-
-            loop {
-                // Check if the current position and direction have been visited
-                if !visited.insert((current_pos, direction_switch % 4)) {
-                    in_loop = 1; // Loop detected
-                    break;
-                }
-
-                // Calculate the new position based on the current direction
-                let current_dir = directions[direction_switch % 4];
-                let new_pos = (current_pos.0 + current_dir.0, current_pos.1 + current_dir.1);
-
-                // Exit if out of bounds
-                if new_pos.0 < 0 || new_pos.1 < 0 || new_pos.0 >= width || new_pos.1 >= height {
-                    break;
-                }
-
-                // Get the current character
-                let current_char = local_map[new_pos.1 as usize][new_pos.0 as usize];
-                if current_char == '#' {
-                    // Switch direction if we hit an obstacle
-                    direction_switch += 1;
-                } else {
-                    // Update the current position
-                    current_pos = new_pos;
-                }
-            }
-
-            in_loop
-        })
-        .sum();
-
-    Some(loop_count)
+    let loop_count = candidates
+        .into_par_iter()
+        // Every thread reuses its bitset for all of its candidates
+        .map_init(
+            || BitSet::new(cells * 4),
+            |visited, obstacle| {
+                visited.clear();
+                is_loop(lab, &jumps, obstacle, visited)
+            },
+        )
+        .filter(|in_loop| *in_loop)
+        .count();
+
+    Some(loop_count as u32)
 }
 
 #[cfg(test)]
@@ -145,13 +193,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_lab(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_lab(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(6));
     }
 }
@@ -7,10 +7,10 @@ use nom::{
     IResult,
 };
 use rayon::prelude::*;
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse = parse_equations);
 
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     target: u64,
     numbers: Vec<u64>,
 }
@@ -34,6 +34,11 @@ fn parse_equation(input: &str) -> IResult<&str, Equation> {
 fn parse_input(input: &str) -> IResult<&str, Vec<Equation>> {
     separated_list1(line_ending, parse_equation)(input)
 }
+// Parsed once for both parts
+fn parse_equations(input: &str) -> Vec<Equation> {
+    let (_, equations) = parse_input(input).expect("Failed to parse input");
+    equations
+}
 
 // Reduces the number slice by tring recursivly to merge the
 // two first entry into a sum or a product
@@ -80,26 +85,21 @@ fn recursive_fold2(numbers: &[u64], target: u64) -> bool {
 
     // Stop if the slice has only two numbers
     if numbers.len() == 2 {
-        if [sum, prod, cat].iter().any(|x| *x == target) {
+        if [sum, prod, cat].contains(&target) {
             return true;
         }
         return false;
     }
 
-    [sum, prod, cat]
-        .iter()
-        .map(|op| {
-            let mut new_numbers = numbers[1..].to_owned();
-            new_numbers[0] = *op;
-            return recursive_fold2(&new_numbers, target);
-        })
-        .any(|x| x)
+    [sum, prod, cat].iter().any(|op| {
+        let mut new_numbers = numbers[1..].to_owned();
+        new_numbers[0] = *op;
+        recursive_fold2(&new_numbers, target)
+    })
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (_, input) = parse_input(input).expect("Failed to parse input");
-
-    input
+pub fn part_one(equations: &[Equation]) -> Option<u64> {
+    equations
         .iter()
         .filter_map(|eq| {
             let out = recursive_fold(&eq.numbers, eq.target);
@@ -112,10 +112,8 @@ pub fn part_one(input: &str) -> Option<u64> {
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, input) = parse_input(input).expect("Failed to parse input");
-
-    input
+pub fn part_two(equations: &[Equation]) -> Option<u64> {
+    equations
         .par_iter()
         .filter_map(|eq| {
             let out = recursive_fold2(&eq.numbers, eq.target);
@@ -134,13 +132,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_equations(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_equations(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(11387));
     }
 }
@@ -1,46 +1,30 @@
 use std::collections::HashMap;
 
+use advent_of_code::grid::Grid;
 use glam::IVec2;
 use itertools::Itertools;
 
 advent_of_code::solution!(8);
 
-fn in_bounds(pos: &IVec2, width: usize, height: usize) -> bool {
-    pos.x >= 0 && pos.y >= 0 && pos.x < width as i32 && pos.y < height as i32
+/// Group the positions of the antennas by frequency
+fn find_frequencies(map: &Grid<char>) -> HashMap<char, Vec<IVec2>> {
+    let mut frequencies_map: HashMap<char, Vec<IVec2>> = HashMap::new();
+    for (pos, f) in map.iter().filter(|(_, c)| **c != '.') {
+        frequencies_map.entry(*f).or_default().push(pos);
+    }
+    frequencies_map
 }
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = input.lines().map(|l| l.chars().collect_vec()).collect_vec();
 
-    let width = map[0].len();
-    let height = map.len();
-
-    // Find the location of the frequencies
-    let frequencies = map
-        .iter()
-        .flatten()
-        .enumerate()
-        .filter_map(|(pos, c)| match c {
-            '.' => None,
-            f => Some((
-                *f,
-                IVec2 {
-                    x: (pos % width) as i32,
-                    y: (pos / width) as i32,
-                },
-            )),
-        })
-        .collect_vec();
+pub fn part_one(input: &str) -> Option<usize> {
+    let map: Grid<char> = input.parse().unwrap();
 
-    // Convert to a Hashmap where the key is the frequency
+    // Hashmap where the key is the frequency
     // and the value is a vec containing all the positions
-    let mut frequencies_map: HashMap<char, Vec<IVec2>> = HashMap::new();
-    for (f, pos) in &frequencies {
-        frequencies_map.entry(*f).or_default().push(*pos);
-    }
+    let frequencies_map = find_frequencies(&map);
 
     frequencies_map
-        .iter()
-        .filter_map(|(_, positions)| {
+        .values()
+        .filter_map(|positions| {
             match positions.len() {
                 1 => None, // Ignore if there is only 1 frequency
                 _ => {
@@ -68,43 +52,21 @@ pub fn part_one(input: &str) -> Option<usize> {
         // remove duplicates and filter the antinodes outside the map
         .unique()
         // Keep only those which are inside the map
-        .filter(|antinode| in_bounds(antinode, width, height))
+        .filter(|antinode| map.in_bounds(*antinode))
         .count()
         .into()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let map = input.lines().map(|l| l.chars().collect_vec()).collect_vec();
+    let map: Grid<char> = input.parse().unwrap();
 
-    let width = map[0].len();
-    let height = map.len();
-    // Find the location of the frequencies
-    let frequencies = map
-        .iter()
-        .flatten()
-        .enumerate()
-        .filter_map(|(pos, c)| match c {
-            '.' => None,
-            f => Some((
-                *f,
-                IVec2 {
-                    x: (pos % width) as i32,
-                    y: (pos / width) as i32,
-                },
-            )),
-        })
-        .collect_vec();
-
-    // Convert to a Hashmap where the key is the frequency
+    // Hashmap where the key is the frequency
     // and the value is a vec containing all the positions
-    let mut frequencies_map: HashMap<char, Vec<IVec2>> = HashMap::new();
-    for (f, pos) in &frequencies {
-        frequencies_map.entry(*f).or_default().push(*pos);
-    }
+    let frequencies_map = find_frequencies(&map);
 
     frequencies_map
-        .iter()
-        .filter_map(|(_, positions)| {
+        .values()
+        .filter_map(|positions| {
             match positions.len() {
                 1 => None, // Ignore if there is only 1 frequency
                 _ => {
@@ -118,22 +80,12 @@ pub fn part_two(input: &str) -> Option<usize> {
                                 let point_b = combi[1];
                                 // Compute the vector between a and b
                                 let vector = point_b - point_a;
-                                // Apply this vector as many times as needed
+                                // Cast this vector as many times as needed
                                 // to both end, in oppsite directions for a
-                                let mut antinodes = Vec::new();
-                                let mut new_point = point_a - vector;
-                                while in_bounds(&new_point, width, height) {
-                                    antinodes.push(new_point);
-                                    new_point -= vector;
-                                }
-                                let mut new_point = point_b + vector;
-                                while in_bounds(&new_point, width, height) {
-                                    antinodes.push(new_point);
-                                    new_point += vector;
-                                }
-                                antinodes.push(*point_a);
-                                antinodes.push(*point_b);
-                                antinodes
+                                map.ray(*point_a, -vector)
+                                    .chain(map.ray(*point_b, vector))
+                                    .chain([*point_a, *point_b])
+                                    .collect_vec()
                             })
                             .collect_vec(),
                     )
@@ -146,7 +98,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         // remove duplicates and filter the antinodes outside the map
         .unique()
         // Keep only those which are inside the map
-        .filter(|antinode| in_bounds(antinode, width, height))
+        .filter(|antinode| map.in_bounds(*antinode))
         .count()
         .into()
 }
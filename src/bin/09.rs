@@ -1,7 +1,9 @@
-use std::{collections::VecDeque, iter::zip};
+use std::collections::{BinaryHeap, VecDeque};
 
-use itertools::Itertools;
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::cmp::{
+    Ordering::{Equal, Greater, Less},
+    Reverse,
+};
 
 advent_of_code::solution!(9);
 
@@ -84,70 +86,59 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let line = input
-        .lines()
-        .next()
-        .unwrap()
-        .chars()
-        .map(|x| x.to_string().parse::<i32>().unwrap());
+/// Largest size of a file or a free span, as the disk map uses a single digit per entry.
+const MAX_SPAN: usize = 9;
 
-    let mut files: VecDeque<(usize, i32)> = line.clone().step_by(2).enumerate().collect();
-    let mut spaces: VecDeque<i32> = line.skip(1).step_by(2).collect();
+/// Sum of `id * position` over the blocks of a file, again with the arithmetic sequence formula.
+fn file_checksum(id: usize, pos: usize, len: usize) -> usize {
+    id * len * (2 * pos + len.saturating_sub(1)) / 2
+}
 
-    return None;
-    dbg!(&files, &spaces);
+pub fn part_two(input: &str) -> Option<usize> {
+    let digits = input.trim().bytes().map(|x| (x - b'0') as usize);
 
-    let mut output: Vec<(usize, i32)> = Vec::new();
-    // Push the first file into the output
-    output.push(files.pop_front().unwrap());
+    // (id, position, length) of every file, and one min-heap of free span positions per span size.
+    // Keeping spans by size means a file only looks at the first span of each size that fits,
+    // instead of rescanning all spans from the left.
+    let mut files: Vec<(usize, usize, usize)> = Vec::new();
+    let mut spans: [BinaryHeap<Reverse<usize>>; MAX_SPAN + 1] = Default::default();
 
-    // // Remove zero-spaces
-    // let mut spaces: VecDeque<i32> = spaces.into_iter().filter(|x| *x != 0).collect();
-    // let mut files: VecDeque<(usize, i32)> = files.into_iter().filter(|x| x.1 != 0).collect();
-    // Move the files into the spaces, until there's no space left on device :P
-    loop {
-        if files.is_empty() || spaces.is_empty() {
-            break;
+    let mut pos = 0;
+    for (i, len) in digits.enumerate() {
+        if i % 2 == 0 {
+            files.push((i / 2, pos, len));
+        } else if len > 0 {
+            spans[len].push(Reverse(pos));
         }
-        // Test
-        // Take the last file
-        for (id, mut file) in files.iter().rev() {
-            // Try all the spaces to put it in
-            let mut has_break = false;
-            for space in spaces.iter_mut() {
-                if space > &mut file {
-                    // The file with go there
-                    // Update the space accordingly
-                    *space -= file;
-                    if *space < 0 {
-                        panic!("cannot happen");
-                    }
-                    if *space == 0 {
-                        spaces.pop_front();
-                    }
+        pos += len;
+    }
 
-                    has_break = true;
-                    break;
-                }
-            }
+    // Move every file once, starting with the highest id
+    for (_, file_pos, len) in files.iter_mut().rev() {
+        // The leftmost span that fits, it has to be left of the file
+        let Some((size, Reverse(span_pos))) = (*len..=MAX_SPAN)
+            .filter_map(|size| spans[size].peek().map(|&span_pos| (size, span_pos)))
+            .filter(|(_, Reverse(span_pos))| span_pos < file_pos)
+            .min_by_key(|(_, Reverse(span_pos))| *span_pos)
+        else {
+            continue;
+        };
+
+        spans[size].pop();
+        *file_pos = span_pos;
+
+        // The rest of the span stays free. The space left behind by the file is never reused:
+        // files only move left, and all files left to move come before it.
+        if size > *len {
+            spans[size - *len].push(Reverse(span_pos + *len));
         }
     }
-    None
-
-    // let mut result = 0;
-    // let mut idx = 0;
-    // for (id, num) in &output {
-    //     // Using arithmetic sequence sum formula
-    //     let n = *num as usize;
-    //     let first_term = idx;
-    //     let last_term = idx + (n - 1);
-    //     let sequence_sum = n * (first_term + last_term) / 2;
-
-    //     result += id * sequence_sum;
-    //     idx += n;
-    // }
-    // Some(result)
+
+    files
+        .iter()
+        .map(|&(id, pos, len)| file_checksum(id, pos, len))
+        .sum::<usize>()
+        .into()
 }
 
 #[cfg(test)]
@@ -188,6 +179,99 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(2858));
+    }
+
+    #[test]
+    fn test_part_two_leftover_space() {
+        let input = "15102";
+        /*
+        0.....122
+        022...1..
+        0221.....
+        2*1 + 2*2 + 1*3 = 9
+        */
+        let result = part_two(input);
+        assert_eq!(result, Some(9));
+    }
+
+    #[test]
+    fn test_part_two_files_never_move_right() {
+        let input = "21352";
+        /*
+        00.111.....22
+        00.11122...
+        1*(3+4+5) + 2*(6+7) = 38
+        */
+        let result = part_two(input);
+        assert_eq!(result, Some(38));
+    }
+
+    /// Block by block simulation of part two, slow but straightforward.
+    fn naive_part_two(input: &str) -> usize {
+        let mut blocks: Vec<Option<usize>> = Vec::new();
+        for (i, len) in input
+            .trim()
+            .bytes()
+            .map(|x| (x - b'0') as usize)
+            .enumerate()
+        {
+            let block = (i % 2 == 0).then_some(i / 2);
+            blocks.extend(std::iter::repeat_n(block, len));
+        }
+
+        let max_id = blocks.iter().flatten().copied().max().unwrap_or(0);
+        for id in (0..=max_id).rev() {
+            let Some(start) = blocks.iter().position(|x| *x == Some(id)) else {
+                continue;
+            };
+            let len = blocks[start..]
+                .iter()
+                .take_while(|x| **x == Some(id))
+                .count();
+
+            // First run of free blocks that fits, left of the file
+            let mut run = 0;
+            for pos in 0..start {
+                run = if blocks[pos].is_none() { run + 1 } else { 0 };
+                if run == len {
+                    let target = pos + 1 - len;
+                    for offset in 0..len {
+                        blocks[target + offset] = Some(id);
+                        blocks[start + offset] = None;
+                    }
+                    break;
+                }
+            }
+        }
+
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(pos, block)| pos * block.unwrap_or(0))
+            .sum()
+    }
+
+    #[test]
+    fn test_part_two_matches_naive_simulation() {
+        let example = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_two(&example), Some(naive_part_two(&example)));
+
+        // Pseudo-random disk maps, with a fixed seed to keep the test reproducible
+        let mut seed: u64 = 0x2024_1209;
+        let mut random = |min: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (b'0' + (min + seed % (10 - min)) as u8) as char
+        };
+
+        for len in [1, 2, 7, 50, 501] {
+            for _ in 0..20 {
+                // Like in the puzzle input, files take at least one block while spans can be empty
+                let input: String = (0..len).map(|i| random(1 - i % 2)).collect();
+                assert_eq!(part_two(&input), Some(naive_part_two(&input)), "{input}");
+            }
+        }
     }
 }
@@ -1,125 +1,54 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    ops::RangeBounds,
-};
-
+use advent_of_code::graph::{bfs, count_paths};
+use advent_of_code::grid::Grid;
 use glam::IVec2;
 use itertools::Itertools;
 use rayon::prelude::*;
 advent_of_code::solution!(10);
 
-// Define the four cardinal directions
-const DIRECTIONS: [IVec2; 4] = [
-    IVec2::new(0, -1), // North
-    IVec2::new(0, 1),  // South
-    IVec2::new(1, 0),  // East
-    IVec2::new(-1, 0), // West
-];
+fn parse_map(input: &str) -> Grid<i32> {
+    Grid::parse(input, |x| x.to_digit(10).unwrap() as i32).unwrap()
+}
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let map = input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|x| x.to_string().parse::<i32>().unwrap())
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let width = map[0].len() as i32;
-    let height = map.len() as i32;
+    let map = &parse_map(input);
+    // Trails only go up by 1 at each step
+    let uphill = |pos: IVec2| {
+        let height = map[pos];
+        map.neighbours_4(pos)
+            .filter(move |next| map[*next] - height == 1)
+    };
 
     // find all trailheads
-    let trailheads = map
-        .iter()
-        .flatten()
-        .positions(|x| *x == 0)
-        .map(|pos| IVec2 {
-            x: pos as i32 % width,
-            y: pos as i32 / width,
-        })
-        .collect_vec();
+    let trailheads = map.positions(|x| *x == 0).collect_vec();
 
     trailheads
         .into_par_iter()
+        // Let's do a BFS for once, every reachable 9 counts once
         .map(|trailhead| {
-            // Let's do a BFS for once
-            let mut to_visit: VecDeque<IVec2> = VecDeque::new();
-            let mut found_nines: HashSet<IVec2> = HashSet::new();
-            to_visit.push_back(trailhead);
-
-            while let Some(cursor) = to_visit.pop_front() {
-                let map_value = map[cursor.y as usize][cursor.x as usize];
-                if map_value == 9 && !found_nines.contains(&cursor) {
-                    found_nines.insert(cursor);
-                    continue;
-                }
-                for direction in DIRECTIONS {
-                    let next_cursor = cursor + direction;
-                    if (0..width).contains(&next_cursor.x) && (0..height).contains(&next_cursor.y) {
-                        let new_map_value = map[next_cursor.y as usize][next_cursor.x as usize];
-                        if new_map_value - map_value == 1 {
-                            to_visit.push_back(next_cursor);
-                        }
-                    }
-                }
-            }
-            found_nines.len()
+            bfs(&uphill, [trailhead])
+                .filter(|(pos, _)| map[*pos] == 9)
+                .count()
         })
         .sum::<usize>()
         .into()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let map = input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|x| x.to_string().parse::<i32>().unwrap())
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let width = map[0].len() as i32;
-    let height = map.len() as i32;
+    let map = &parse_map(input);
+    // Trails only go up by 1 at each step
+    let uphill = |pos: IVec2| {
+        let height = map[pos];
+        map.neighbours_4(pos)
+            .filter(move |next| map[*next] - height == 1)
+    };
 
     // find all trailheads
-    let trailheads = map
-        .iter()
-        .flatten()
-        .positions(|x| *x == 0)
-        .map(|pos| IVec2 {
-            x: pos as i32 % width,
-            y: pos as i32 / width,
-        })
-        .collect_vec();
+    let trailheads = map.positions(|x| *x == 0).collect_vec();
 
     trailheads
         .into_par_iter()
-        .map(|trailhead| {
-            // Let's do a BFS for once
-            let mut to_visit: VecDeque<IVec2> = VecDeque::new();
-            let mut found_nines: Vec<IVec2> = Vec::new();
-            to_visit.push_back(trailhead);
-
-            while let Some(cursor) = to_visit.pop_front() {
-                let map_value = map[cursor.y as usize][cursor.x as usize];
-                if map_value == 9 {
-                    found_nines.push(cursor);
-                    continue;
-                }
-                for direction in DIRECTIONS {
-                    let next_cursor = cursor + direction;
-                    if (0..width).contains(&next_cursor.x) && (0..height).contains(&next_cursor.y) {
-                        let new_map_value = map[next_cursor.y as usize][next_cursor.x as usize];
-                        if new_map_value - map_value == 1 {
-                            to_visit.push_back(next_cursor);
-                        }
-                    }
-                }
-            }
-            found_nines.len()
-        })
+        // Every distinct trail to a 9 counts, the trails can't loop since they only go uphill
+        .map(|trailhead| count_paths(&uphill, trailhead, |pos| map[*pos] == 9).unwrap() as usize)
         .sum::<usize>()
         .into()
 }
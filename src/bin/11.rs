@@ -1,48 +1,116 @@
-use itertools::Itertools;
-use memoize::memoize;
-use rayon::prelude::*;
+use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+use num::{BigUint, CheckedAdd, One, Zero};
 
-fn solve(input: &str, max_depth: i32) -> Option<u64> {
-    let input = input
-        .lines()
-        .next()
-        .unwrap()
+advent_of_code::solution!(11, parse = parse_stones);
+
+/// Blinks of part two, unless another count is passed with `--param blinks=<n>`.
+const BLINKS: usize = 75;
+
+/// The engraved values of the stones, and how often part two blinks at them.
+pub struct Arrangement {
+    pub values: Vec<u64>,
+    pub blinks: usize,
+}
+
+/// Parses the stones, the blink count is read here so the parts do not read the arguments.
+fn parse_stones(input: &str) -> Arrangement {
+    let values = input
         .split_whitespace()
         .map(|x| x.parse::<u64>().unwrap())
-        .collect_vec();
+        .collect();
+    let blinks = advent_of_code::template::param("blinks").unwrap_or(BLINKS);
 
-    #[memoize]
-    fn process_stone(stone: u64, depth: i32, max: i32) -> u64 {
-        if depth == max {
-            return 1;
-        }
-        if stone == 0 {
-            return process_stone(1, depth + 1, max);
-        }
-        let num_digits = stone.ilog10() + 1;
-        if num_digits % 2 == 0 {
-            let divisor = 10_u64.pow(num_digits / 2);
-            let (left, right) = (stone / divisor, stone % divisor);
-            return process_stone(left, depth + 1, max) + process_stone(right, depth + 1, max);
+    Arrangement { values, blinks }
+}
+
+/// A number of stones, from `u64` for the puzzle up to `BigUint` for thousands of blinks.
+pub trait Count: Zero + One + Clone + CheckedAdd {}
+
+impl<T: Zero + One + Clone + CheckedAdd> Count for T {}
+
+/// Number of stones of every engraved value. Stones never interact and their order does not
+/// change the count, so all stones with the same value evolve together.
+pub type Stones<C> = HashMap<u64, C>;
+
+/// The values a single stone turns into after a blink.
+fn blink_stone(value: u64) -> (u64, Option<u64>) {
+    if value == 0 {
+        return (1, None);
+    }
+    let num_digits = value.ilog10() + 1;
+    if num_digits.is_multiple_of(2) {
+        let divisor = 10_u64.pow(num_digits / 2);
+        return (value / divisor, Some(value % divisor));
+    }
+    (value * 2024, None)
+}
+
+/// The stones after one more blink, `None` if a count no longer fits in `C`.
+pub fn blink<C: Count>(stones: &Stones<C>) -> Option<Stones<C>> {
+    let mut next = Stones::with_capacity(stones.len());
+
+    for (&value, count) in stones {
+        let (left, right) = blink_stone(value);
+        for value in std::iter::once(left).chain(right) {
+            let total = next.entry(value).or_insert_with(C::zero);
+            *total = total.checked_add(count)?;
         }
-        process_stone(stone * 2024, depth + 1, max)
     }
 
-    input
-        .into_par_iter()
-        .map(|stone| process_stone(stone, 0, max_depth))
-        .sum::<u64>()
-        .into()
+    Some(next)
+}
+
+/// The stones engraved with `values`, before blinking.
+pub fn arrange<C: Count>(values: &[u64]) -> Stones<C> {
+    let mut stones = Stones::new();
+    for &value in values {
+        let count = stones.entry(value).or_insert_with(C::zero);
+        *count = count.clone() + C::one();
+    }
+    stones
+}
+
+/// `stones` after 0, 1, 2... blinks. Ends early once a count no longer fits in `C`.
+pub fn distributions<C: Count>(stones: Stones<C>) -> impl Iterator<Item = Stones<C>> {
+    std::iter::successors(Some(stones), blink)
+}
+
+/// Total number of `stones`, `None` if it does not fit in `C`.
+fn total<C: Count>(stones: &Stones<C>) -> Option<C> {
+    stones
+        .values()
+        .try_fold(C::zero(), |total, count| total.checked_add(count))
+}
+
+/// Number of stones after `blinks` blinks, `None` if it does not fit in `C`.
+pub fn count_stones<C: Count>(values: &[u64], blinks: usize) -> Option<C> {
+    total(&distributions(arrange(values)).nth(blinks)?)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    solve(input, 25)
+pub fn part_one(arrangement: &Arrangement) -> Option<u64> {
+    count_stones(&arrangement.values, 25)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    solve(input, 75)
+pub fn part_two(arrangement: &Arrangement) -> Option<BigUint> {
+    let blinks = arrangement.blinks;
+
+    // Counts only outgrow `u64` after about a hundred blinks and the big integers are much
+    // slower, so blink with `u64` as long as the counts fit.
+    let (done, stones) = distributions::<u64>(arrange(&arrangement.values))
+        .take(blinks + 1)
+        .enumerate()
+        .last()?;
+    if let Some(count) = total(&stones).filter(|_| done == blinks) {
+        return Some(count.into());
+    }
+
+    // then carry on from there with `BigUint`.
+    let stones: Stones<BigUint> = stones
+        .into_iter()
+        .map(|(value, count)| (value, count.into()))
+        .collect();
+    total(&distributions(stones).nth(blinks - done)?)
 }
 
 #[cfg(test)]
@@ -51,13 +119,64 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_stones(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482));
+        let result = part_two(&parse_stones(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
+        assert_eq!(result, Some(BigUint::from(65601038650482_u64)));
+    }
+
+    #[test]
+    fn test_distributions() {
+        let mut distributions = distributions::<u64>(arrange(&[125, 17]));
+        assert_eq!(
+            distributions.next(),
+            Some(Stones::from([(125, 1), (17, 1)]))
+        );
+        assert_eq!(
+            distributions.next(),
+            Some(Stones::from([(253000, 1), (1, 1), (7, 1)]))
+        );
+
+        // 2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2
+        let stones = distributions.nth(4).unwrap();
+        assert_eq!(stones.values().sum::<u64>(), 22);
+        assert_eq!(stones[&2], 4);
+        assert_eq!(stones[&40], 2);
+        assert_eq!(stones[&0], 2);
+    }
+
+    #[test]
+    fn test_large_counts() {
+        assert_eq!(count_stones::<u8>(&[125, 17], 6), Some(22));
+        assert_eq!(count_stones::<u8>(&[125, 17], 25), None);
+
+        // Too many stones for `u64`, but not for `u128`
+        let values = [125, 17];
+        assert_eq!(count_stones::<u64>(&values, 150), None);
+        let count = count_stones::<u128>(&values, 150).unwrap();
+        assert_eq!(count_stones::<BigUint>(&values, 150), Some(count.into()));
+    }
+
+    #[test]
+    fn test_part_two_past_u64() {
+        // After 108 blinks only the total outgrows `u64`, after 150 the counts do on the way
+        for blinks in [108, 150] {
+            let arrangement = Arrangement {
+                values: vec![125, 17],
+                blinks,
+            };
+            assert_eq!(
+                part_two(&arrangement),
+                count_stones::<BigUint>(&arrangement.values, blinks)
+            );
+        }
     }
 }
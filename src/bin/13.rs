@@ -1,38 +1,45 @@
-use glam::IVec2;
-use itertools::{iproduct, Itertools};
+use advent_of_code::linalg::{solve_2x2, solve_diophantine, Solution2x2};
+use glam::I64Vec2;
 use nom::{
     bytes::complete::{tag, take_until},
-    character::complete::{anychar, digit1, line_ending, multispace0, multispace1, one_of, space1},
+    character::complete::{anychar, digit1, multispace0, multispace1},
     combinator::{map, map_res},
-    complete::take,
-    multi::{many_till, separated_list1},
     sequence::{preceded, separated_pair, tuple},
     IResult,
 };
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, parse = parse_games);
+
+/// Tokens it costs to press the A and the B button.
+const COSTS: [i128; 2] = [3, 1];
+
+/// Part one never presses a button more than this often.
+const MAX_PRESSES: i128 = 100;
+
+/// Part two moves all prizes this far away on both axes.
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
 
 #[derive(Debug)]
-struct Game {
-    button_a: IVec2,
-    button_b: IVec2,
-    prize: IVec2,
+pub struct Game {
+    button_a: I64Vec2,
+    button_b: I64Vec2,
+    prize: I64Vec2,
 }
 
 // Parse the input
-// Parses a single i32 from a string slice
-fn parse_i32(input: &str) -> IResult<&str, i32> {
+// Parses a single i64 from a string slice
+fn parse_i64(input: &str) -> IResult<&str, i64> {
     map_res(digit1, str::parse)(input)
 }
-fn parse_coord_component(input: &str) -> IResult<&str, i32> {
-    preceded(tuple((anychar, anychar)), parse_i32)(input)
+fn parse_coord_component(input: &str) -> IResult<&str, i64> {
+    preceded(tuple((anychar, anychar)), parse_i64)(input)
 }
-fn parse_coords(input: &str) -> IResult<&str, IVec2> {
+fn parse_coords(input: &str) -> IResult<&str, I64Vec2> {
     let (parsed, (x, y)) =
         separated_pair(parse_coord_component, tag(", "), parse_coord_component)(input)?;
-    Ok((parsed, IVec2 { x, y }))
+    Ok((parsed, I64Vec2 { x, y }))
 }
-fn parse_line(input: &str) -> IResult<&str, IVec2> {
+fn parse_line(input: &str) -> IResult<&str, I64Vec2> {
     preceded(tuple((take_until(": "), tag(": "))), parse_coords)(input)
 }
 fn parse_game(input: &str) -> IResult<&str, Game> {
@@ -64,50 +71,167 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Game>> {
     Ok((remaining, games))
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
+// Parsed once for both parts
+fn parse_games(input: &str) -> Vec<Game> {
     let (_, games) = parse_input(input).expect("Failed to parse input");
-
     games
+}
+
+/// Presses of the A and B button that win the prize for the fewest tokens, pressing each button at
+/// most `max_presses` times if given.
+/// Pressing `a` and `b` times reaches `a * button_a + b * button_b`, which gives one linear
+/// equation per axis.
+fn cheapest_presses(game: &Game, max_presses: Option<i128>) -> Option<[i128; 2]> {
+    let Game {
+        button_a,
+        button_b,
+        prize,
+    } = game;
+
+    match solve_2x2(
+        [[button_a.x, button_b.x], [button_a.y, button_b.y]],
+        [prize.x, prize.y],
+    ) {
+        Solution2x2::Unique(presses) => Some(presses).filter(|p| {
+            p.iter()
+                .all(|x| *x >= 0 && max_presses.is_none_or(|max| *x <= max))
+        }),
+        Solution2x2::Fractional | Solution2x2::Inconsistent => None,
+        // Both buttons move in the same direction as the prize, so both axes give the same
+        // equation. Use one that actually moves, and pick the cheapest of its solutions within
+        // the bound, the cheapest solution overall might take too many presses.
+        Solution2x2::Dependent => {
+            let axis = if button_a.x != 0 || button_b.x != 0 {
+                0
+            } else {
+                1
+            };
+            solve_diophantine(button_a[axis], button_b[axis], prize[axis])?
+                .min_within(COSTS, max_presses)
+        }
+    }
+}
+
+fn tokens(presses: [i128; 2]) -> i128 {
+    COSTS[0] * presses[0] + COSTS[1] * presses[1]
+}
+
+pub fn part_one(games: &[Game]) -> Option<i64> {
+    let tokens: i128 = games
         .iter()
-        .filter_map(|game| {
-            // Compute maximum number of times we need to push the buttons
-            let max_a_press = 101; //(game.prize / game.button_a).max_element();
-            let max_b_press = 101; //(game.prize / game.button_b).max_element();
-
-            let costs = iproduct!(1..max_a_press, 1..max_b_press)
-                .filter_map(|(a_press, b_press)| {
-                    let cost = 3 * a_press + b_press;
-                    let pos = a_press * game.button_a + b_press * game.button_b;
-                    if pos == game.prize {
-                        return Some(cost);
-                    }
-                    None
-                })
-                .collect_vec();
-
-            costs.into_iter().min()
-        })
-        .sum::<i32>()
-        .into()
+        .filter_map(|game| cheapest_presses(game, Some(MAX_PRESSES)))
+        .map(tokens)
+        .sum();
+
+    i64::try_from(tokens).ok()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(games: &[Game]) -> Option<i64> {
+    let tokens: i128 = games
+        .iter()
+        .map(|game| Game {
+            prize: game.prize + PRIZE_OFFSET,
+            ..*game
+        })
+        .filter_map(|game| cheapest_presses(&game, None))
+        .map(tokens)
+        .sum();
+
+    i64::try_from(tokens).ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example() -> Vec<Game> {
+        parse_games(&advent_of_code::template::read_file("examples", DAY))
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&example());
         assert_eq!(result, Some(480));
     }
 
-    // #[test]
-    // fn test_part_two() {
-    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-    //     assert_eq!(result, None);
-    // }
+    #[test]
+    fn test_part_two() {
+        // Only the second and the fourth machine can be won, the puzzle does not give the total
+        let games = example();
+        let presses = games
+            .iter()
+            .map(|game| {
+                cheapest_presses(
+                    &Game {
+                        prize: game.prize + PRIZE_OFFSET,
+                        ..*game
+                    },
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            presses,
+            vec![
+                None,
+                Some([118679050709, 103199174542]),
+                None,
+                Some([102851800151, 107526881786]),
+            ]
+        );
+
+        let result = part_two(&games);
+        assert_eq!(result, Some(875318608908));
+    }
+
+    #[test]
+    fn test_collinear_buttons() {
+        let game = |button_a, button_b, prize| Game {
+            button_a: I64Vec2::from_array(button_a),
+            button_b: I64Vec2::from_array(button_b),
+            prize: I64Vec2::from_array(prize),
+        };
+        let cheapest_presses_of = |game: &Game| cheapest_presses(game, None);
+
+        // A moves twice as far as B for three times the cost, only B is pressed
+        assert_eq!(
+            cheapest_presses_of(&game([2, 2], [1, 1], [10, 10])),
+            Some([0, 10])
+        );
+        // A moves four times as far as B, it is cheaper to press A as often as possible
+        assert_eq!(
+            cheapest_presses_of(&game([8, 4], [2, 1], [22, 11])),
+            Some([2, 3])
+        );
+        // The prize is off the line of the buttons
+        assert_eq!(cheapest_presses_of(&game([2, 2], [1, 1], [5, 6])), None);
+        // The prize is on the line, but out of reach with whole presses
+        assert_eq!(cheapest_presses_of(&game([4, 2], [6, 3], [2, 1])), None);
+        // Buttons only move along y
+        assert_eq!(
+            cheapest_presses_of(&game([0, 4], [0, 3], [0, 10])),
+            Some([1, 2])
+        );
+    }
+
+    #[test]
+    fn test_collinear_buttons_with_max_presses() {
+        let game = Game {
+            button_a: I64Vec2::new(2, 2),
+            button_b: I64Vec2::new(1, 1),
+            prize: I64Vec2::new(150, 150),
+        };
+
+        // Only pressing B is the cheapest, but takes 150 presses: press A enough to stay within 100
+        assert_eq!(cheapest_presses(&game, None), Some([0, 150]));
+        assert_eq!(cheapest_presses(&game, Some(MAX_PRESSES)), Some([25, 100]));
+
+        // Even A alone takes more than 100 presses
+        let far = Game {
+            prize: I64Vec2::new(400, 400),
+            ..game
+        };
+        assert_eq!(cheapest_presses(&far, Some(MAX_PRESSES)), None);
+        assert_eq!(part_one(&[game, far]), Some(175));
+    }
 }
@@ -0,0 +1,5 @@
+advent_of_code::registry!();
+
+fn main() {
+    advent_of_code::template::registry::run(&solutions::all());
+}
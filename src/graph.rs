@@ -0,0 +1,259 @@
+/// Generic graph algorithms over anything that can list the successors of a node.
+/// Adjacency maps implement [`Graph`] directly. For a [`Grid`](crate::grid::Grid), pass a closure
+/// that picks the neighbours to walk to, e.g. `|pos: IVec2| grid.neighbours_4(pos)`.
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::hash::{BuildHasher, Hash};
+
+/// A directed graph.
+pub trait Graph<N> {
+    /// Nodes that have an edge from `node`.
+    fn successors(&self, node: N) -> impl Iterator<Item = N>;
+}
+
+impl<N: Clone + Eq + Hash, S: BuildHasher> Graph<N> for HashMap<N, Vec<N>, S> {
+    fn successors(&self, node: N) -> impl Iterator<Item = N> {
+        self.get(&node).into_iter().flatten().cloned()
+    }
+}
+
+impl<N, I: IntoIterator<Item = N>, F: Fn(N) -> I> Graph<N> for F {
+    fn successors(&self, node: N) -> impl Iterator<Item = N> {
+        self(node).into_iter()
+    }
+}
+
+/// Nodes that form a cycle, in the order of their edges. The last node has an edge to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Debug> Error for Cycle<N> {}
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph contains a cycle: ")?;
+        for node in &self.0 {
+            write!(f, "{node:?} -> ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    /// On the current DFS path.
+    Open,
+    Done,
+}
+
+/// Sorts all nodes reachable from `roots` so that every node comes before its successors.
+/// Among unrelated nodes, the nodes reached from an earlier root come first.
+pub fn topo_sort<N, G>(graph: &G, roots: impl IntoIterator<Item = N>) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let mut marks: HashMap<N, Mark> = HashMap::new();
+    let mut order = vec![];
+
+    // the order is reversed at the end, so the last root is visited first.
+    let roots: Vec<N> = roots.into_iter().collect();
+    for root in roots.into_iter().rev() {
+        if marks.contains_key(&root) {
+            continue;
+        }
+
+        marks.insert(root.clone(), Mark::Open);
+        let mut stack = vec![(root.clone(), graph.successors(root))];
+
+        while let Some((_, successors)) = stack.last_mut() {
+            let Some(next) = successors.next() else {
+                let (node, _) = stack.pop().unwrap();
+                marks.insert(node.clone(), Mark::Done);
+                order.push(node);
+                continue;
+            };
+
+            match marks.get(&next) {
+                None => {
+                    marks.insert(next.clone(), Mark::Open);
+                    stack.push((next.clone(), graph.successors(next)));
+                }
+                Some(Mark::Open) => {
+                    let start = stack.iter().position(|(node, _)| *node == next).unwrap();
+                    return Err(Cycle(
+                        stack[start..]
+                            .iter()
+                            .map(|(node, _)| node.clone())
+                            .collect(),
+                    ));
+                }
+                Some(Mark::Done) => {}
+            }
+        }
+    }
+
+    // nodes are done after all their successors.
+    order.reverse();
+    Ok(order)
+}
+
+/// Visits every node reachable from `starts` once, in breadth-first order.
+/// Yields each node with its distance to the closest start.
+pub fn bfs<'a, N, G>(
+    graph: &'a G,
+    starts: impl IntoIterator<Item = N>,
+) -> impl Iterator<Item = (N, usize)> + 'a
+where
+    N: 'a,
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let mut queue: VecDeque<(N, usize)> = starts.into_iter().map(|node| (node, 0)).collect();
+    let mut visited: HashSet<N> = queue.iter().map(|(node, _)| node.clone()).collect();
+
+    std::iter::from_fn(move || {
+        let (node, depth) = queue.pop_front()?;
+
+        for next in graph.successors(node.clone()) {
+            if visited.insert(next.clone()) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+
+        Some((node, depth))
+    })
+}
+
+/// Visits every node reachable from `start` once, in depth-first pre-order.
+pub fn dfs<'a, N, G>(graph: &'a G, start: N) -> impl Iterator<Item = N> + 'a
+where
+    N: 'a,
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let mut stack = vec![start];
+    let mut visited: HashSet<N> = HashSet::new();
+
+    std::iter::from_fn(move || loop {
+        let node = stack.pop()?;
+
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+
+        // push in reverse, so the first successor is visited first.
+        let successors: Vec<N> = graph
+            .successors(node.clone())
+            .filter(|next| !visited.contains(next))
+            .collect();
+        stack.extend(successors.into_iter().rev());
+
+        return Some(node);
+    })
+}
+
+/// Counts the distinct paths from `start` that end in a node matching `is_target`.
+/// A path may pass through targets on its way to another target.
+pub fn count_paths<N, G>(
+    graph: &G,
+    start: N,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Result<u64, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let order = topo_sort(graph, [start.clone()])?;
+    let mut paths: HashMap<N, u64> = HashMap::with_capacity(order.len());
+
+    // successors come later in the order, count them first.
+    for node in order.into_iter().rev() {
+        let through = graph
+            .successors(node.clone())
+            .map(|next| paths[&next])
+            .sum::<u64>();
+        let count = through + u64::from(is_target(&node));
+        paths.insert(node, count);
+    }
+
+    Ok(paths[&start])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bfs, count_paths, dfs, topo_sort, Cycle};
+    use crate::grid::Grid;
+    use glam::IVec2;
+    use std::collections::HashMap;
+
+    fn diamond() -> HashMap<u32, Vec<u32>> {
+        HashMap::from([(1, vec![2, 3]), (2, vec![4]), (3, vec![4])])
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let order = topo_sort(&diamond(), [1, 2, 3, 4]).unwrap();
+        assert_eq!(order.len(), 4);
+        assert_eq!(order[0], 1);
+        assert_eq!(order[3], 4);
+    }
+
+    #[test]
+    fn sorts_unrelated_nodes_by_root() {
+        let graph = HashMap::from([(1, vec![2]), (3, vec![4]), (5, vec![6])]);
+        assert_eq!(
+            topo_sort(&graph, [3, 1, 5]).unwrap(),
+            vec![3, 4, 1, 2, 5, 6]
+        );
+        assert_eq!(topo_sort(&graph, [1, 2, 3]).unwrap(), vec![1, 2, 3, 4]);
+        // edges win over the order of the roots.
+        assert_eq!(topo_sort(&graph, [2, 1]).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn returns_cycles() {
+        let graph = HashMap::from([(1, vec![2]), (2, vec![3]), (3, vec![1]), (0, vec![1])]);
+        let Err(Cycle(cycle)) = topo_sort(&graph, [0]) else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_eq!(
+            Cycle(cycle).to_string(),
+            "graph contains a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+
+    #[test]
+    fn traverses_graphs() {
+        let graph = diamond();
+        assert_eq!(
+            bfs(&graph, [1]).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (3, 1), (4, 2)]
+        );
+        assert_eq!(dfs(&graph, 1).collect::<Vec<_>>(), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn counts_paths() {
+        assert_eq!(count_paths(&diamond(), 1, |n| *n == 4), Ok(2));
+        assert_eq!(count_paths(&diamond(), 1, |n| *n >= 2), Ok(4));
+        assert_eq!(count_paths(&diamond(), 4, |n| *n == 1), Ok(0));
+    }
+
+    #[test]
+    fn walks_grids() {
+        let grid: Grid<char> = "..#\n.##\n...".parse().unwrap();
+        let open = |pos: IVec2| grid.neighbours_4(pos).filter(|next| grid[*next] == '.');
+
+        let distances: HashMap<IVec2, usize> = bfs(&open, [IVec2::new(0, 0)]).collect();
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&IVec2::new(2, 2)], 4);
+    }
+}
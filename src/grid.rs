@@ -0,0 +1,307 @@
+/// A dense two-dimensional grid, as found in most puzzle inputs.
+/// Positions are `glam::IVec2` with `x` growing to the right and `y` growing downwards.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use glam::IVec2;
+
+/// The four orthogonal directions: up, right, down, left.
+pub const DIRECTIONS_4: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+];
+
+/// The eight directions including diagonals, clockwise starting with up.
+pub const DIRECTIONS_8: [IVec2; 8] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line and one cell per character.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+
+            match width {
+                None => width = Some(cells.len()),
+                Some(width) if width != cells.len() - len => {
+                    return Err(GridError::RaggedRow { row })
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid::new(width, height, cells)),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` lies inside the grid.
+    pub fn in_bounds(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Index of `pos` in the row-major cells, if it lies inside the grid.
+    pub fn index_of(&self, pos: IVec2) -> Option<usize> {
+        self.in_bounds(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Position of the cell at `index` in the row-major cells.
+    pub fn pos_of(&self, index: usize) -> IVec2 {
+        IVec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    /// All cells and their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.pos_of(i), cell))
+    }
+
+    /// The first position whose cell matches `predicate`, in row-major order.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.pos_of(i))
+    }
+
+    /// All positions whose cell matches `predicate`, in row-major order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours_4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .map(move |dir| pos + dir)
+            .filter(|next| self.in_bounds(*next))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours_8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .map(move |dir| pos + dir)
+            .filter(|next| self.in_bounds(*next))
+    }
+
+    /// Positions from `start` in steps of `dir` until the edge of the grid, excluding `start`.
+    pub fn ray(&self, start: IVec2, dir: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        let mut pos = start;
+        std::iter::from_fn(move || {
+            pos += dir;
+            self.in_bounds(pos).then_some(pos)
+        })
+    }
+
+    /// Creates a grid of the same dimensions by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid::new(width, height, vec![value; width * height])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |c| c)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.cells.chunks(self.width).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// A row has a different length than the first row.
+    RaggedRow {
+        row: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => f.write_str("expecting at least one row"),
+            GridError::RaggedRow { row } => {
+                write!(f, "expecting row {row} to be as long as the first row")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+    use glam::IVec2;
+
+    const INPUT: &str = "#..\n.#.\n..X\n";
+
+    #[test]
+    fn parses_and_displays() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[IVec2::new(2, 2)], 'X');
+        assert_eq!(grid.to_string(), INPUT.trim_end());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+        assert_eq!(
+            "##\n#\n".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow { row: 1 })
+        );
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = Grid::parse("123\n456", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid.get(IVec2::new(1, 1)), Some(&5));
+        assert_eq!(grid.get(IVec2::new(3, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid.pos_of(5), IVec2::new(2, 1));
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.find(|c| *c == 'X'), Some(IVec2::new(2, 2)));
+        assert_eq!(
+            grid.positions(|c| *c == '#').collect::<Vec<_>>(),
+            vec![IVec2::new(0, 0), IVec2::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = Grid::filled(3, 3, 0);
+        assert_eq!(grid.neighbours_4(IVec2::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours_4(IVec2::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_8(IVec2::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours_8(IVec2::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = Grid::filled(4, 4, 0);
+        assert_eq!(
+            grid.ray(IVec2::new(0, 0), IVec2::new(1, 1))
+                .collect::<Vec<_>>(),
+            vec![IVec2::new(1, 1), IVec2::new(2, 2), IVec2::new(3, 3)]
+        );
+        assert_eq!(grid.ray(IVec2::new(3, 0), IVec2::new(1, 0)).count(), 0);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod graph;
+pub mod grid;
+pub mod linalg;
+pub mod memo;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
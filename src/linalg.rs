@@ -0,0 +1,279 @@
+/// Exact integer linear algebra for the small systems of equations found in puzzles.
+/// Everything is computed on `i128`, so products of coordinates around `10^13` do not overflow.
+use std::cmp::Ordering;
+
+/// Solutions of a 2×2 system of linear equations `m * [x, y] = rhs`, see [`solve_2x2`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solution2x2 {
+    /// The only solution, with integer components.
+    Unique([i128; 2]),
+    /// The only solution has a component that is not an integer.
+    Fractional,
+    /// The equations contradict each other, there is no solution.
+    Inconsistent,
+    /// The equations are dependent, the solutions form a line or the whole plane.
+    /// Integer solutions of one of the equations can be found with [`solve_diophantine`].
+    Dependent,
+}
+
+/// Determinant of a 2×2 matrix, given as rows.
+pub fn det_2x2(m: [[i64; 2]; 2]) -> i128 {
+    i128::from(m[0][0]) * i128::from(m[1][1]) - i128::from(m[0][1]) * i128::from(m[1][0])
+}
+
+/// Solves `m * [x, y] = rhs` with Cramer's rule, where `m` is given as rows.
+pub fn solve_2x2(m: [[i64; 2]; 2], rhs: [i64; 2]) -> Solution2x2 {
+    let det = det_2x2(m);
+    let det_x = det_2x2([[rhs[0], m[0][1]], [rhs[1], m[1][1]]]);
+    let det_y = det_2x2([[m[0][0], rhs[0]], [m[1][0], rhs[1]]]);
+
+    if det != 0 {
+        return if det_x % det == 0 && det_y % det == 0 {
+            Solution2x2::Unique([det_x / det, det_y / det])
+        } else {
+            Solution2x2::Fractional
+        };
+    }
+
+    // NOTE: with a zero determinant, the system has solutions iff the augmented matrix has rank
+    // at most one. A zero matrix only has solutions for a zero right-hand side.
+    let is_zero_matrix = m.iter().flatten().all(|x| *x == 0);
+    let is_consistent = if is_zero_matrix {
+        rhs == [0, 0]
+    } else {
+        det_x == 0 && det_y == 0
+    };
+
+    if is_consistent {
+        Solution2x2::Dependent
+    } else {
+        Solution2x2::Inconsistent
+    }
+}
+
+/// Greatest common divisor `g >= 0` of `a` and `b`, with Bézout coefficients `a * x + b * y = g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// All integer solutions of a linear equation in two unknowns:
+/// `x = x0 + k * dx` and `y = y0 - k * dy` for every integer `k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineSolutions {
+    pub x0: i128,
+    pub y0: i128,
+    pub dx: i128,
+    pub dy: i128,
+}
+
+impl LineSolutions {
+    /// The solution for a given `k`.
+    pub fn at(&self, k: i128) -> [i128; 2] {
+        [self.x0 + k * self.dx, self.y0 - k * self.dy]
+    }
+
+    /// The solution with `x >= 0` and `y >= 0` that minimizes `weights[0] * x + weights[1] * y`,
+    /// e.g. the cheapest way to reach a target with two kinds of steps.
+    /// Weights must not be negative, or the minimum might not exist.
+    pub fn min_nonnegative(&self, weights: [i128; 2]) -> Option<[i128; 2]> {
+        self.min_within(weights, None)
+    }
+
+    /// Like [`LineSolutions::min_nonnegative`], with both components at most `max` if given,
+    /// e.g. when each kind of step can only be taken so often.
+    pub fn min_within(&self, weights: [i128; 2], max: Option<i128>) -> Option<[i128; 2]> {
+        debug_assert!(weights.iter().all(|w| *w >= 0));
+
+        let (mut lower, mut upper): (Option<i128>, Option<i128>) = (None, None);
+        let tighten =
+            |upper: Option<i128>, bound: i128| Some(upper.map_or(bound, |u| u.min(bound)));
+
+        // each component `0 <= v0 + k * dv <= max` bounds `k` from both sides.
+        for (v0, dv) in [(self.x0, self.dx), (self.y0, -self.dy)] {
+            match dv.cmp(&0) {
+                Ordering::Equal if v0 < 0 || max.is_some_and(|max| v0 > max) => return None,
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    lower = lower.max(Some(div_ceil(-v0, dv)));
+                    if let Some(max) = max {
+                        upper = tighten(upper, div_floor(max - v0, dv));
+                    }
+                }
+                Ordering::Less => {
+                    upper = tighten(upper, div_floor(v0, -dv));
+                    if let Some(max) = max {
+                        lower = lower.max(Some(div_ceil(v0 - max, -dv)));
+                    }
+                }
+            }
+        }
+
+        if let (Some(lower), Some(upper)) = (lower, upper) {
+            if lower > upper {
+                return None;
+            }
+        }
+
+        // the cost changes linearly with `k`, so the minimum is at one of the bounds.
+        let slope = weights[0] * self.dx - weights[1] * self.dy;
+        let k = if slope > 0 {
+            lower.or(upper)
+        } else {
+            upper.or(lower)
+        };
+
+        Some(self.at(k.unwrap_or(0)))
+    }
+}
+
+/// All integer solutions of `a * x + b * y = c`. Returns `None` if there are none, or if `a` and
+/// `b` are both zero, as every pair then solves the equation (for `c == 0`) or none does.
+pub fn solve_diophantine(a: i64, b: i64, c: i64) -> Option<LineSolutions> {
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    let (g, x, y) = extended_gcd(a, b);
+
+    if g == 0 || c % g != 0 {
+        return None;
+    }
+
+    Some(LineSolutions {
+        x0: x * (c / g),
+        y0: y * (c / g),
+        dx: b / g,
+        dy: a / g,
+    })
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extended_gcd, solve_2x2, solve_diophantine, LineSolutions, Solution2x2};
+
+    #[test]
+    fn solves_unique_systems() {
+        // 94a + 22b = 8400, 34a + 67b = 5400
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Solution2x2::Unique([80, 40])
+        );
+        assert_eq!(
+            solve_2x2([[26, 67], [66, 21]], [12748, 12176]),
+            Solution2x2::Fractional
+        );
+    }
+
+    #[test]
+    fn solves_large_systems() {
+        let offset = 10_000_000_000_000;
+        assert_eq!(
+            solve_2x2([[26, 67], [66, 21]], [offset + 12748, offset + 12176]),
+            Solution2x2::Unique([118_679_050_709, 103_199_174_542])
+        );
+    }
+
+    #[test]
+    fn detects_degenerate_systems() {
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), Solution2x2::Dependent);
+        assert_eq!(
+            solve_2x2([[1, 2], [2, 4]], [3, 7]),
+            Solution2x2::Inconsistent
+        );
+        assert_eq!(solve_2x2([[0, 0], [0, 0]], [0, 0]), Solution2x2::Dependent);
+        assert_eq!(
+            solve_2x2([[0, 0], [0, 0]], [0, 1]),
+            Solution2x2::Inconsistent
+        );
+    }
+
+    #[test]
+    fn computes_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (0, 7), (7, 0), (0, 0), (17, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert!(g >= 0);
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(extended_gcd(240, 46).0, 2);
+    }
+
+    #[test]
+    fn solves_diophantine_equations() {
+        let line = solve_diophantine(4, 6, 20).unwrap();
+        for k in -3..=3 {
+            let [x, y] = line.at(k);
+            assert_eq!(4 * x + 6 * y, 20);
+        }
+        assert_eq!(solve_diophantine(4, 6, 21), None);
+        assert_eq!(solve_diophantine(0, 0, 0), None);
+    }
+
+    #[test]
+    fn finds_cheapest_nonnegative_solutions() {
+        // 2x + 4y = 12 with x, y >= 0: (6, 0), (4, 1), (2, 2), (0, 3)
+        let line = solve_diophantine(2, 4, 12).unwrap();
+        assert_eq!(line.min_nonnegative([3, 1]), Some([0, 3]));
+        assert_eq!(line.min_nonnegative([1, 3]), Some([6, 0]));
+
+        // 3x + 5y = 4 has integer solutions, but none without negative components.
+        let line = solve_diophantine(3, 5, 4).unwrap();
+        assert_eq!(line.min_nonnegative([1, 1]), None);
+
+        // x is free when its coefficient is zero, y has to be 2.
+        let line = LineSolutions {
+            x0: -4,
+            y0: 2,
+            dx: 1,
+            dy: 0,
+        };
+        assert_eq!(line.min_nonnegative([3, 1]), Some([0, 2]));
+    }
+
+    #[test]
+    fn finds_cheapest_bounded_solutions() {
+        // 2x + y = 150 with x, y >= 0: the cheapest (0, 150) takes too many y.
+        let line = solve_diophantine(2, 1, 150).unwrap();
+        assert_eq!(line.min_nonnegative([3, 1]), Some([0, 150]));
+        assert_eq!(line.min_within([3, 1], Some(100)), Some([25, 100]));
+        assert_eq!(line.min_within([1, 3], Some(100)), Some([75, 0]));
+        assert_eq!(line.min_within([3, 1], Some(50)), Some([50, 50]));
+        assert_eq!(line.min_within([3, 1], Some(49)), None);
+
+        // y is fixed when its coefficient is zero, and has to be within the bound as well.
+        let line = LineSolutions {
+            x0: -4,
+            y0: 2,
+            dx: 1,
+            dy: 0,
+        };
+        assert_eq!(line.min_within([3, 1], Some(2)), Some([0, 2]));
+        assert_eq!(line.min_within([3, 1], Some(1)), None);
+    }
+}
@@ -1,40 +1,61 @@
+use advent_of_code::template::commands::scaffold::{DEFAULT_ANSWER_TYPE, DEFAULT_TEMPLATE};
+use advent_of_code::template::commands::solve::SolutionArgs;
 use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::{PuzzleId, Year};
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{parse_param, Day, Year};
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
             day: Day,
+            year: Option<Year>,
         },
         Read {
             day: Day,
+            year: Option<Year>,
         },
         Scaffold {
             day: Day,
+            year: Option<Year>,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
             day: Day,
+            year: Option<Year>,
             release: bool,
             dhat: bool,
+            allocations: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            time: bool,
+            params: Vec<(String, String)>,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+            allocations: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
+            year: Option<Year>,
             store: bool,
+            compare: bool,
+            history: Option<Day>,
+            timeout: Option<Duration>,
+            allocations: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,34 +66,58 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: args.opt_value_from_str("--year")?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                allocations: args.contains("--allocations"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let year = args.opt_value_from_str("--year")?;
+                let compare = args.contains("--compare");
+                let history = args.opt_value_from_str("--history")?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_timeout)?;
+                let allocations = args.contains("--allocations");
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
+                    year,
                     store,
+                    compare,
+                    history,
+                    timeout,
+                    allocations,
                 }
             }
             Some("download") => AppArguments::Download {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
             },
             Some("solve") => AppArguments::Solve {
+                year: args.opt_value_from_str("--year")?,
+                input: args.opt_value_from_str("--input")?,
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                allocations: args.contains("--allocations"),
+                time: args.contains("--time"),
+                params: args.values_from_fn("--param", parse_param)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +138,25 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses a timeout in seconds, fractions are allowed.
+    fn parse_timeout(s: &str) -> Result<Duration, String> {
+        s.parse::<f64>()
+            .ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .filter(|timeout| !timeout.is_zero())
+            .ok_or_else(|| format!("expected a positive number of seconds, got `{s}`."))
+    }
+}
+
+/// Falls back to the year configured through `AOC_YEAR` when `--year` was not passed.
+fn resolve_year(year: Option<Year>) -> Year {
+    year.or_else(Year::configured).unwrap_or_else(|| {
+        eprintln!(
+            "No year specified. Pass `--year <year>` or set AOC_YEAR in `.cargo/config.toml`."
+        );
+        process::exit(1);
+    })
 }
 
 fn main() {
@@ -102,33 +166,92 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                timeout,
+                allocations,
+            } => all::handle(resolve_year(year), release, jobs, timeout, allocations),
+            AppArguments::Time {
+                day,
+                year,
+                all,
+                store,
+                compare,
+                history: None,
+                timeout,
+                allocations,
+            } => time::handle(
+                resolve_year(year),
+                day,
+                all,
+                store,
+                compare,
+                timeout,
+                allocations,
+            ),
+            AppArguments::Time {
+                year,
+                history: Some(day),
+                ..
+            } => time::handle_history(resolve_year(year), day),
+            AppArguments::Download { day, year } => {
+                download::handle(PuzzleId::new(resolve_year(year), day));
+            }
+            AppArguments::Read { day, year } => {
+                read::handle(PuzzleId::new(resolve_year(year), day));
+            }
             AppArguments::Scaffold {
                 day,
+                year,
                 download,
                 overwrite,
+                template,
+                answer_type,
             } => {
-                scaffold::handle(day, overwrite);
+                let puzzle = PuzzleId::new(resolve_year(year), day);
+                // download first, so the examples of the description end up in the scaffold.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(
+                    puzzle,
+                    overwrite,
+                    template.as_deref().unwrap_or(DEFAULT_TEMPLATE),
+                    answer_type.as_deref().unwrap_or(DEFAULT_ANSWER_TYPE),
+                );
             }
             AppArguments::Solve {
                 day,
+                year,
                 release,
                 dhat,
+                allocations,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                time,
+                params,
+            } => solve::handle(
+                PuzzleId::new(resolve_year(year), day),
+                release,
+                dhat,
+                allocations,
+                &SolutionArgs {
+                    submit,
+                    input,
+                    time,
+                    params,
+                },
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::new(resolve_year(None), day);
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, false, DEFAULT_TEMPLATE, DEFAULT_ANSWER_TYPE);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,172 @@
+/// Memoization caches for recursive solutions.
+/// A cache lives as long as its owner: create a [`Memo`] or [`ConcurrentMemo`] inside a part to
+/// scope it to a single call, so every benchmark sample starts from scratch instead of timing the
+/// cache hits of the previous one. The runner knows nothing about these caches.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::sync::Mutex;
+
+/// A cache of values by key, shared by the recursive calls of a function.
+pub trait Cache<K, V> {
+    /// The cached value of `key`, or the value of `f` which is then cached.
+    /// `f` may use the cache itself, no lock is held while it runs.
+    fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V;
+
+    /// Removes all cached values.
+    fn clear(&self);
+
+    /// Number of cached values.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Single-threaded cache.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    values: RefCell<HashMap<K, V>>,
+}
+
+impl<K, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            values: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Cache<K, V> for Memo<K, V> {
+    fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.values.borrow().get(&key) {
+            return value.clone();
+        }
+
+        let value = f();
+        self.values.borrow_mut().insert(key, value.clone());
+        value
+    }
+
+    fn clear(&self) {
+        self.values.borrow_mut().clear();
+    }
+
+    fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+}
+
+/// Number of independently locked parts of a [`ConcurrentMemo`].
+const SHARDS: usize = 16;
+
+/// Cache that can be shared between threads, e.g. by the items of a `par_iter`.
+/// Keys are spread over several locked maps, so threads rarely wait for each other. Threads that
+/// miss the same key at the same time both compute its value.
+#[derive(Debug)]
+pub struct ConcurrentMemo<K, V> {
+    shards: [Mutex<HashMap<K, V>>; SHARDS],
+    hasher: RandomState,
+}
+
+impl<K, V> ConcurrentMemo<K, V> {
+    pub fn new() -> Self {
+        ConcurrentMemo {
+            shards: std::array::from_fn(|_| Mutex::new(HashMap::new())),
+            hasher: RandomState::new(),
+        }
+    }
+}
+
+impl<K, V> Default for ConcurrentMemo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> ConcurrentMemo<K, V> {
+    fn shard(&self, key: &K) -> &Mutex<HashMap<K, V>> {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.hasher.hash_one(key) as usize % SHARDS;
+        &self.shards[index]
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Cache<K, V> for ConcurrentMemo<K, V> {
+    fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        let shard = self.shard(&key);
+
+        if let Some(value) = shard.lock().unwrap().get(&key) {
+            return value.clone();
+        }
+
+        let value = f();
+        shard.lock().unwrap().insert(key, value.clone());
+        value
+    }
+
+    fn clear(&self) {
+        for shard in &self.shards {
+            shard.lock().unwrap().clear();
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.lock().unwrap().len()).sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cache, ConcurrentMemo, Memo};
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Number of ways to climb `n` stairs with steps of one or two, counting the computed values.
+    fn stairs(memo: &impl Cache<u64, u64>, calls: &AtomicUsize, n: u64) -> u64 {
+        if n < 2 {
+            return 1;
+        }
+        memo.get_or_insert_with(n, || {
+            calls.fetch_add(1, Ordering::Relaxed);
+            stairs(memo, calls, n - 1) + stairs(memo, calls, n - 2)
+        })
+    }
+
+    #[test]
+    fn memoizes_recursive_calls() {
+        let memo = Memo::new();
+        let calls = AtomicUsize::new(0);
+        assert_eq!(stairs(&memo, &calls, 80), 37_889_062_373_143_906);
+        assert_eq!(calls.load(Ordering::Relaxed), 79);
+        assert_eq!(memo.len(), 79);
+
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn shares_values_between_threads() {
+        let memo = ConcurrentMemo::new();
+        let calls = AtomicUsize::new(0);
+        let results: Vec<u64> = (0..64)
+            .into_par_iter()
+            .map(|n| stairs(&memo, &calls, n))
+            .collect();
+
+        assert_eq!(results[10], 89);
+        assert_eq!(results[63], 10_610_209_857_723);
+        assert_eq!(memo.len(), 62);
+        // racing threads may compute a value twice, but never for every call.
+        assert!(calls.load(Ordering::Relaxed) < 62 * 8);
+    }
+}
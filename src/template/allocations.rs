@@ -0,0 +1,232 @@
+/// Counting global allocator, enabled with the `count-allocations` feature.
+/// It wraps the system allocator and keeps a few atomic counters, so the runner can report how
+/// many allocations a part made, how many bytes it allocated and how large its heap grew.
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tinyjson::JsonValue;
+
+#[cfg(all(feature = "count-allocations", feature = "dhat-heap"))]
+compile_error!("`count-allocations` and `dhat-heap` both replace the global allocator.");
+
+/// Cargo feature that installs the counting allocator, forwarded by `--allocations`.
+pub const FEATURE: &str = "count-allocations";
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+/// Heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Allocations {
+    /// Number of allocations, reallocations included.
+    pub count: u64,
+    /// Sum of the sizes of all allocations.
+    pub bytes: u64,
+    /// Largest size of the heap during the run, on top of what was allocated before it.
+    pub peak_bytes: u64,
+}
+
+impl Allocations {
+    /// Short summary for the output of a part, e.g. `12 allocs, 4.0KiB, peak 1.0KiB`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} allocs, {}, peak {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use super::{ALLOCATED_BYTES, ALLOCATIONS, CURRENT_BYTES, PEAK_BYTES};
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::Ordering,
+    };
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    struct CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+}
+
+/// Run `f` and count its allocations. Returns `None` unless the `count-allocations` feature is on.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !cfg!(feature = "count-allocations") {
+        return (f(), None);
+    }
+
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+
+    let result = f();
+
+    let allocations = Allocations {
+        count: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline),
+    };
+
+    (result, Some(allocations))
+}
+
+/// Formats a number of bytes with binary units, e.g. `1.5KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Allocations> for JsonValue {
+    fn from(value: Allocations) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("count".into(), JsonValue::Number(value.count as f64));
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Allocations {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocations to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected allocations.{key} to be a number."))
+        };
+
+        Ok(Allocations {
+            count: number("count")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/// Reads an optional allocations field of a JSON object, missing fields count as null.
+pub fn optional_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<Allocations>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => Allocations::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, Allocations};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024 * 1024), "5120.0GiB");
+    }
+
+    #[test]
+    fn summarizes_allocations() {
+        let allocations = Allocations {
+            count: 12,
+            bytes: 4096,
+            peak_bytes: 1024,
+        };
+        assert_eq!(allocations.summary(), "12 allocs, 4.0KiB, peak 1.0KiB");
+    }
+
+    #[test]
+    fn round_trips_allocations() {
+        let allocations = Allocations {
+            count: 3,
+            bytes: 1 << 40,
+            peak_bytes: 0,
+        };
+        let json = JsonValue::from(allocations);
+        assert_eq!(Allocations::try_from(&json).unwrap(), allocations);
+    }
+}
@@ -0,0 +1,184 @@
+/// Module that stores known-correct answers to protect solutions against regressions.
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, PuzzleId};
+
+static ANSWERS_DIR: &str = "answers";
+
+/// Correct answers of a single day, stored as `data/answers/{day}.json`,
+/// or `data/{year}/answers/{day}.json` for years in their own directories.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Outcome of comparing a result with the stored answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// No answer was stored for this part yet.
+    Unknown,
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+}
+
+impl Answers {
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file(puzzle: PuzzleId) -> Self {
+        let s = match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => s,
+            Err(_) => return Answers::default(),
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        fs::create_dir_all(data_dir(puzzle.year).join(ANSWERS_DIR))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    pub fn verify(&self, part: u8, answer: &str) -> Verification {
+        match self.get(part) {
+            None => Verification::Unknown,
+            Some(expected) if expected == answer => Verification::Correct,
+            Some(expected) => Verification::Incorrect {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/// Remember `answer` as the correct answer for a part of a day.
+pub fn store(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file(puzzle);
+    answers.set(part, answer);
+    answers.store_file(puzzle)
+}
+
+fn get_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path(ANSWERS_DIR, "json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", value.part_1), ("part_2", value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_2 to be null or string.")?;
+
+        Ok(Answers {
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verification};
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "part_1": "42", "part_2": null }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.part_1, Some("42".to_string()));
+        assert_eq!(answers.part_2, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "part_1": 42 }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_multiline_answers() {
+        let answers = Answers {
+            part_1: Some("#..#\n.##.".into()),
+            part_2: None,
+        };
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let mut answers = Answers::default();
+        answers.set(1, "42");
+        assert_eq!(answers.verify(1, "42"), Verification::Correct);
+        assert_eq!(
+            answers.verify(1, "41"),
+            Verification::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.verify(2, "42"), Verification::Unknown);
+    }
+}
@@ -0,0 +1,439 @@
+/// Client for the adventofcode.com website.
+/// Downloads inputs and puzzle descriptions and submits answers without any external tools.
+use std::{env, fmt::Display, fs, path::PathBuf, sync::OnceLock, time::Duration};
+
+use regex::Regex;
+
+use crate::template::PuzzleId;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable that overrides the website, e.g. to test against a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable that holds the session cookie. Takes precedence over the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Session file in the home directory, shared with `aoc-cli`.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+/// The website asks automated tools to identify themselves.
+const USER_AGENT: &str = "github.com/thibthibaut/aoc-2024 (advent_of_code template)";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    /// The website answered with a non-success HTTP status.
+    Status(u16),
+    Transport(String),
+    UnexpectedResponse(&'static str),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or create the file ~/{SESSION_FILE}."
+            ),
+            AocClientError::Status(400) => {
+                write!(
+                    f,
+                    "the website rejected the request, is the session cookie still valid?"
+                )
+            }
+            AocClientError::Status(404) => {
+                write!(f, "the website returned 404, is the puzzle unlocked yet?")
+            }
+            AocClientError::Status(status) => write!(f, "the website returned status {status}."),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::Status(status),
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// Outcome of submitting an answer, parsed from the message of the website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// An answer was submitted too recently, nothing was checked.
+    Wait,
+    /// The part was already solved, nothing was checked.
+    AlreadySolved,
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if message.contains("your answer is too high") {
+            SubmissionOutcome::TooHigh
+        } else if message.contains("your answer is too low") {
+            SubmissionOutcome::TooLow
+        } else if message.contains("That's not the right answer") {
+            SubmissionOutcome::Incorrect
+        } else if message.contains("You gave an answer too recently") {
+            SubmissionOutcome::Wait
+        } else if message.contains("Did you already complete it") {
+            SubmissionOutcome::AlreadySolved
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+
+    /// Whether the website checked the answer against the solution.
+    pub fn is_checked(self) -> bool {
+        matches!(
+            self,
+            SubmissionOutcome::Correct
+                | SubmissionOutcome::TooHigh
+                | SubmissionOutcome::TooLow
+                | SubmissionOutcome::Incorrect
+        )
+    }
+}
+
+/// Response of the website to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub outcome: SubmissionOutcome,
+    /// Message of the website as plain text.
+    pub message: String,
+}
+
+/// Description of a puzzle, as shown on the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    /// Name of the puzzle, e.g. `Historian Hysteria`.
+    pub title: Option<String>,
+    /// HTML of the description of each part that is unlocked.
+    pub parts: Vec<String>,
+}
+
+impl Puzzle {
+    /// Markdown document of the description. Keeps the HTML of the website, which is valid markdown.
+    pub fn to_markdown(&self) -> String {
+        self.parts.join("\n\n")
+    }
+
+    /// Plain text rendering of the description for the terminal.
+    pub fn to_text(&self) -> String {
+        html_to_text(&self.to_markdown())
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        AocClient {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+        }
+    }
+
+    /// Configure the client from `AOC_SESSION` (or the session file) and `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &session))
+    }
+
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let response = self.get(&format!("{}/input", self.day_url(puzzle)))?;
+        response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string()))
+    }
+
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<Puzzle, AocClientError> {
+        let response = self.get(&self.day_url(puzzle))?;
+        let html = response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string()))?;
+
+        let parts = extract_articles(&html);
+        if parts.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "puzzle page has no description.",
+            ));
+        }
+
+        Ok(Puzzle {
+            title: extract_title(&html),
+            parts,
+        })
+    }
+
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string()))?;
+
+        let message = extract_articles(&html)
+            .first()
+            .map(|article| html_to_text(article))
+            .ok_or(AocClientError::UnexpectedResponse(
+                "answer page has no message.",
+            ))?;
+
+        Ok(Submission {
+            outcome: SubmissionOutcome::from_message(&message),
+            message,
+        })
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn get(&self, url: &str) -> Result<ureq::Response, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?)
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session).filter(|s| !s.trim().is_empty());
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE)).ok()?;
+    Some(session).filter(|s| !s.trim().is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn extract_articles(html: &str) -> Vec<String> {
+    static ARTICLE: OnceLock<Regex> = OnceLock::new();
+    ARTICLE
+        .get_or_init(|| Regex::new(r"(?s)<article[^>]*>.*?</article>").unwrap())
+        .find_iter(html)
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+pub(crate) fn extract_title(html: &str) -> Option<String> {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    TITLE
+        .get_or_init(|| Regex::new(r"<h2[^>]*>--- Day \d+: (.*?) ---</h2>").unwrap())
+        .captures(html)
+        .map(|c| decode_entities(&c[1]))
+}
+
+/// Strip tags from the HTML of the website, keeping paragraphs and list items on their own lines.
+fn html_to_text(html: &str) -> String {
+    static BLOCK_END: OnceLock<Regex> = OnceLock::new();
+    static LIST_ITEM: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+
+    let text = BLOCK_END
+        .get_or_init(|| Regex::new(r"</(p|h2|pre|ul|article)>").unwrap())
+        .replace_all(html, "$0\n");
+    let text = LIST_ITEM
+        .get_or_init(|| Regex::new(r"<li>").unwrap())
+        .replace_all(&text, "- ");
+    let text = TAG
+        .get_or_init(|| Regex::new(r"<[^>]+>").unwrap())
+        .replace_all(&text, "");
+
+    decode_entities(text.trim())
+}
+
+pub(crate) fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, Puzzle, SubmissionOutcome};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Stand-in for the website that answers a single request and returns it.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, server) = serve_once("200 OK", "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "secret\n");
+
+        assert_eq!(
+            client.input(PuzzleId::new(year!(2024), day!(1))).unwrap(),
+            "1 2\n3 4\n"
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 12: Garden &amp; Groups ---</h2><p>Some <code>text</code>.</p><ul><li>a</li></ul></article><p>footer</p></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let puzzle = client.puzzle(PuzzleId::new(year!(2024), day!(12))).unwrap();
+        assert_eq!(puzzle.title, Some("Garden & Groups".into()));
+        assert_eq!(puzzle.parts.len(), 1);
+        assert_eq!(
+            puzzle.to_text(),
+            "--- Day 12: Garden & Groups ---\nSome text.\n- a"
+        );
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /2024/day/12 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let submission = client
+            .submit(PuzzleId::new(year!(2024), day!(3)), 2, "42")
+            .unwrap();
+        assert_eq!(submission.outcome, SubmissionOutcome::Correct);
+        assert_eq!(submission.message, "That's the right answer!");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn returns_http_status() {
+        let (base_url, server) = serve_once("404 Not Found", "Not found");
+        let client = AocClient::new(&base_url, "secret");
+
+        let res = client.input(PuzzleId::new(year!(2024), day!(25)));
+        assert!(matches!(res, Err(AocClientError::Status(404))));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", SubmissionOutcome::Correct),
+            ("That's not the right answer; your answer is too high. If you're stuck...", SubmissionOutcome::TooHigh),
+            ("That's not the right answer; your answer is too low.", SubmissionOutcome::TooLow),
+            ("That's not the right answer. If you're stuck, make sure...", SubmissionOutcome::Incorrect),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 31s left to wait.", SubmissionOutcome::Wait),
+            ("You don't seem to be solving the right level.  Did you already complete it?", SubmissionOutcome::AlreadySolved),
+            ("Something else.", SubmissionOutcome::Unknown),
+        ];
+
+        for (message, outcome) in cases {
+            assert_eq!(SubmissionOutcome::from_message(message), outcome);
+        }
+    }
+
+    #[test]
+    fn renders_puzzle_markdown() {
+        let puzzle = Puzzle {
+            title: None,
+            parts: vec!["<article>a</article>".into(), "<article>b</article>".into()],
+        };
+        assert_eq!(
+            puzzle.to_markdown(),
+            "<article>a</article>\n\n<article>b</article>"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+/// Module that renders the stored benchmarks as an SVG bar chart, embedded in the readme next to
+/// the benchmark table. Times span several orders of magnitude, so the y-axis is logarithmic.
+use std::fmt::Write;
+use std::{fs, io};
+
+use crate::template::{root_dir, timings::Timings};
+
+/// Path of the chart, relative to the repository root like the links of the readme.
+pub static CHART_PATH: &str = ".assets/benchmarks.svg";
+
+const PLOT_HEIGHT: f64 = 240.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const BAR_WIDTH: f64 = 12.0;
+const GROUP_WIDTH: f64 = 36.0;
+const LEGEND_SPACING: f64 = 70.0;
+
+const PART_COLORS: [&str; 2] = ["#f5a623", "#4a90d9"];
+const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+
+/// Write the chart of `timings` to `.assets/benchmarks.svg`.
+pub fn store(timings: &Timings) -> Result<(), io::Error> {
+    let path = root_dir().join(CHART_PATH);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, render(timings))
+}
+
+/// Powers of ten below the fastest and above the slowest part, at least one decade apart.
+fn decade_range(timings: &Timings) -> (i32, i32) {
+    let nanos = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_1, t.part_2])
+        .flatten()
+        .map(|part| part.nanos.max(1.0));
+
+    let (min, max) = nanos.fold((f64::MAX, f64::MIN), |(min, max), x| {
+        (min.min(x), max.max(x))
+    });
+
+    if min > max {
+        return (0, 3);
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (low, high) = (min.log10().floor() as i32, max.log10().ceil() as i32);
+    (low, high.max(low + 1))
+}
+
+/// Label of a power of ten of nanoseconds, e.g. `10µs` for 4.
+fn decade_label(exponent: i32) -> String {
+    match usize::try_from(exponent / 3) {
+        Ok(unit) if unit < UNITS.len() => {
+            format!("{}{}", 10_u64.pow((exponent % 3) as u32), UNITS[unit])
+        }
+        _ => format!("1e{exponent}ns"),
+    }
+}
+
+/// Render a bar per part, grouped by day, on a logarithmic scale.
+pub fn render(timings: &Timings) -> String {
+    let (low, high) = decade_range(timings);
+    // wide enough for the legend, even with a single day.
+    let plot_width =
+        (GROUP_WIDTH * timings.data.len() as f64).max(LEGEND_SPACING * PART_COLORS.len() as f64);
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let baseline = MARGIN_TOP + PLOT_HEIGHT;
+
+    // height of a bar above the bottom of the plot.
+    let scale = |nanos: f64| {
+        (nanos.max(1.0).log10() - f64::from(low)) / f64::from(high - low) * PLOT_HEIGHT
+    };
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    );
+
+    // legend
+    for (i, color) in PART_COLORS.iter().enumerate() {
+        let x = MARGIN_LEFT + LEGEND_SPACING * i as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="12" width="10" height="10" fill="{color}"/><text x="{}" y="21">Part {}</text>"#,
+            x + 14.0,
+            i + 1
+        );
+    }
+
+    // grid lines at every power of ten
+    for exponent in low..=high {
+        let y = baseline - f64::from(exponent - low) / f64::from(high - low) * PLOT_HEIGHT;
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{y}" x2="{}" y2="{y}" stroke="#dddddd"/><text x="{}" y="{}" text-anchor="end">{}</text>"##,
+            width - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            decade_label(exponent)
+        );
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        let group_x = MARGIN_LEFT + GROUP_WIDTH * i as f64;
+
+        for (part, (timing_part, color)) in [timing.part_1, timing.part_2]
+            .iter()
+            .zip(PART_COLORS)
+            .enumerate()
+        {
+            let Some(timing_part) = timing_part else {
+                continue;
+            };
+
+            let bar_height = scale(timing_part.nanos);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{BAR_WIDTH}" height="{bar_height}" fill="{color}"><title>Day {} part {}: {:.1?}</title></rect>"#,
+                group_x + 6.0 + BAR_WIDTH * part as f64,
+                baseline - bar_height,
+                timing.day.into_inner(),
+                part + 1,
+                timing_part.duration()
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            group_x + 6.0 + BAR_WIDTH,
+            baseline + 16.0,
+            timing.day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{baseline}" x2="{}" y2="{baseline}" stroke="#888888"/>"##,
+        width - MARGIN_RIGHT
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle">Day</text>"#,
+        MARGIN_LEFT + (width - MARGIN_LEFT - MARGIN_RIGHT) / 2.0,
+        height - 6.0
+    );
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decade_label, decade_range, render};
+    use crate::day;
+    use crate::template::timings::{DayStatus, PartTiming, Timing, Timings};
+
+    fn part(nanos: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos,
+            stddev_nanos: None,
+            samples: None,
+            allocations: None,
+        })
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: part(450.0),
+                    part_2: part(2_500_000.0),
+                },
+                Timing {
+                    day: day!(3),
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: part(80_000.0),
+                    part_2: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn spans_all_timings() {
+        assert_eq!(decade_range(&get_mock_timings()), (2, 7));
+        assert_eq!(decade_range(&Timings::default()), (0, 3));
+    }
+
+    #[test]
+    fn labels_decades() {
+        assert_eq!(decade_label(0), "1ns");
+        assert_eq!(decade_label(4), "10µs");
+        assert_eq!(decade_label(8), "100ms");
+        assert_eq!(decade_label(10), "10s");
+        assert_eq!(decade_label(12), "1e12ns");
+    }
+
+    #[test]
+    fn renders_a_bar_per_part() {
+        let svg = render(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("<title>Day 1 part 2: 2.5ms</title>"));
+        assert!(svg.contains(">100ns</text>"));
+        assert!(svg.contains(">10ms</text>"));
+    }
+
+    #[test]
+    fn fits_the_legend_with_few_days() {
+        let mut timings = get_mock_timings();
+        timings.data.truncate(1);
+        let svg = render(&timings);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="220""#));
+        assert!(svg.contains(r#"<text x="148" y="21">Part 2</text>"#));
+
+        let mut timings = get_mock_timings();
+        timings.data = timings.data.into_iter().cycle().take(10).collect();
+        assert!(render(&timings).contains(r#"width="440""#));
+    }
+}
@@ -1,5 +1,25 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days, run_multi::run_multi, Year};
+
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    count_allocations: bool,
+) {
+    let summary = run_multi(
+        year,
+        &all_days().collect(),
+        is_release,
+        false,
+        jobs,
+        timeout,
+        count_allocations,
+    );
+
+    if !summary.mismatches.is_empty() || summary.has_failures() {
+        process::exit(1);
+    }
 }
@@ -1,14 +1,56 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::{aoc_client::AocClient, relative_to_root, PuzzleId};
+
+pub fn handle(puzzle: PuzzleId) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let input = client.input(puzzle).unwrap_or_else(|e| {
+        eprintln!("failed to download input: {e}");
         process::exit(1);
+    });
+
+    let description = client.puzzle(puzzle).unwrap_or_else(|e| {
+        eprintln!("failed to download puzzle: {e}");
+        process::exit(1);
+    });
+
+    for dir in [input_path.parent(), puzzle_path.parent()]
+        .into_iter()
+        .flatten()
+    {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("failed to create directory: {e}");
+            process::exit(1);
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = fs::write(&input_path, input) {
+        eprintln!("failed to write input: {e}");
         process::exit(1);
-    };
+    }
+
+    if let Err(e) = fs::write(&puzzle_path, description.to_markdown()) {
+        eprintln!("failed to write puzzle: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        relative_to_root(&input_path).display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        relative_to_root(&puzzle_path).display()
+    );
 }
@@ -1,15 +1,34 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client::AocClient, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: PuzzleId) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let description = match client.puzzle(puzzle) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("failed to read puzzle: {e}");
+            process::exit(1);
+        }
     };
+
+    // keep the puzzle file up to date, e.g. once part two is unlocked.
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+    let written = puzzle_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&puzzle_path, description.to_markdown()));
+
+    if let Err(e) = written {
+        eprintln!("failed to write puzzle: {e}");
+    }
+
+    println!("{}", description.to_text());
 }
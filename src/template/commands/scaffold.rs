@@ -1,15 +1,18 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client::extract_title, puzzle_examples::PuzzleExamples, relative_to_root,
+    scaffold_template, Layout, PuzzleId,
+};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+pub use crate::template::scaffold_template::{DEFAULT_ANSWER_TYPE, DEFAULT_TEMPLATE};
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +22,11 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +34,159 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Write a data file of the puzzle, or create it empty without `contents`.
+/// Files that already have contents, e.g. a downloaded input, are only replaced with new contents
+/// when overwriting.
+fn write_data_file(path: &Path, name: &str, contents: Option<&str>, overwrite: bool) {
+    let is_empty = fs::metadata(path).map_or(true, |m| m.len() == 0);
+    let replace = overwrite && contents.is_some();
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
+    if !is_empty && !replace {
+        println!(
+            "Kept existing {name} file \"{}\"",
+            relative_to_root(path).display()
+        );
+        return;
+    }
+
+    match create_file(path).and_then(|mut file| file.write_all(contents.unwrap_or("").as_bytes())) {
+        Ok(()) if contents.is_some() => {
+            println!(
+                "Wrote {name} of the puzzle to \"{}\"",
+                relative_to_root(path).display()
+            );
+        }
+        Ok(()) => {
+            println!(
+                "Created empty {name} file \"{}\"",
+                relative_to_root(path).display()
+            );
+        }
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to create {name} file: {e}");
             process::exit(1);
         }
+    }
+}
+
+/// Expected answer of a test. Answers that do not fit the answer type are left to you.
+fn expected_answer(answer: Option<&str>, answer_type: &str, part: u8) -> String {
+    let Some(answer) = answer else {
+        return "None".into();
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    scaffold_template::answer_literal(answer, answer_type).unwrap_or_else(|| {
+        println!("The example answer `{answer}` of part {part} is not a {answer_type}, add it to the test yourself.");
+        "None".into()
+    })
+}
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: &str, answer_type: &str) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let part_two_example_path = puzzle.part_data_path("examples", 2, "txt");
+    let module_path = puzzle.bin_path();
+
+    let template = match scaffold_template::load(template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
+    };
+
+    // examples and title can only be found once the description was downloaded.
+    let description = fs::read_to_string(puzzle.data_path("puzzles", "md")).ok();
+    let examples = description
+        .as_deref()
+        .map(PuzzleExamples::parse)
+        .unwrap_or_default();
+    let title = description
+        .as_deref()
+        .and_then(extract_title)
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+    let part_two_example = if examples.part_two_input.is_some() {
+        "read_puzzle_file_part(\"examples\", PUZZLE, 2)"
+    } else {
+        "read_puzzle_file(\"examples\", PUZZLE)"
+    };
+
+    // solutions of the flat layout take their year from `AOC_YEAR`.
+    let solution_args = match puzzle.layout() {
+        Layout::Flat => puzzle.day.into_inner().to_string(),
+        Layout::Yearly => format!("{}, year = {}", puzzle.day.into_inner(), puzzle.year),
+    };
+
+    let (contents, unknown) = scaffold_template::render(
+        &template,
+        &[
+            ("%SOLUTION_ARGS%", solution_args),
+            ("%DAY_NUMBER%", puzzle.day.into_inner().to_string()),
+            ("%DAY%", puzzle.day.to_string()),
+            ("%YEAR%", puzzle.year.to_string()),
+            ("%TITLE%", title),
+            ("%ANSWER_TYPE%", answer_type.to_string()),
+            ("%PART_TWO_EXAMPLE%", part_two_example.to_string()),
+            (
+                "%PART_ONE_ANSWER%",
+                expected_answer(examples.answers[0].as_deref(), answer_type, 1),
+            ),
+            (
+                "%PART_TWO_ANSWER%",
+                expected_answer(examples.answers[1].as_deref(), answer_type, 2),
+            ),
+        ],
+    );
+
+    for placeholder in unknown {
+        println!("The template has an unknown placeholder {placeholder}, it was left as is.");
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    let mut file = match safe_create_file(&module_path, overwrite) {
+        Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match file.write_all(contents.as_bytes()) {
+        Ok(()) => {
+            println!(
+                "Created module file \"{}\"",
+                relative_to_root(&module_path).display()
+            );
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
 
+    write_data_file(&input_path, "input", None, overwrite);
+    write_data_file(
+        &example_path,
+        "example",
+        examples.input.as_deref(),
+        overwrite,
+    );
+
+    if let Some(part_two_input) = examples.part_two_input.as_deref() {
+        write_data_file(
+            &part_two_example_path,
+            "part two example",
+            Some(part_two_input),
+            overwrite,
+        );
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match puzzle.layout() {
+        Layout::Flat => println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day),
+        Layout::Yearly => println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        ),
+    }
 }
@@ -1,9 +1,53 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{allocations, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Options that are passed through to the solution binary.
+#[derive(Debug)]
+pub struct SolutionArgs {
+    pub submit: Option<u8>,
+    pub input: Option<String>,
+    /// Bench the parts instead of running them once.
+    pub time: bool,
+    /// `(name, value)` of every `--param`.
+    pub params: Vec<(String, String)>,
+}
+
+impl SolutionArgs {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(submit_part) = self.submit {
+            args.push("--submit".to_string());
+            args.push(submit_part.to_string());
+        }
+
+        if let Some(input) = &self.input {
+            args.push("--input".to_string());
+            args.push(input.clone());
+        }
+
+        if self.time {
+            args.push("--time".to_string());
+        }
+
+        for (name, value) in &self.params {
+            args.push("--param".to_string());
+            args.push(format!("{name}={value}"));
+        }
+
+        args
+    }
+}
+
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    count_allocations: bool,
+    solution_args: &SolutionArgs,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,13 +60,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
-    cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if count_allocations {
+        cmd_args.extend(["--features".to_string(), allocations::FEATURE.to_string()]);
     }
 
+    cmd_args.push("--".to_string());
+    cmd_args.extend(solution_args.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,16 +1,30 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process, time::Duration};
 
+use crate::template::compare::{self, Change};
+use crate::template::history::{self, History};
+use crate::template::report::PartReport;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Layout, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: bool,
+    timeout: Option<Duration>,
+    count_allocations: bool,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare {
+                // when comparing, bench the days that have a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,20 +35,87 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let summary = run_multi(
+        year,
+        &days_to_run,
+        true,
+        true,
+        1,
+        timeout,
+        count_allocations,
+    );
+    let has_failures = summary.has_failures();
+    let timings = summary.timings.unwrap();
+
+    if !summary.mismatches.is_empty() {
+        if store {
+            eprintln!("Not storing benchmarks of solutions with wrong answers.");
+        }
+        process::exit(1);
+    }
+
+    let has_regressions = compare && {
+        let diffs = compare::compare(&stored_timings, &timings);
+        compare::print_diffs(&diffs);
+        report_regressions(&diffs)
+    };
+
+    if has_regressions {
+        if store {
+            eprintln!("Not storing benchmarks of solutions that got slower.");
+        }
+        process::exit(1);
+    }
 
+    // days that failed or timed out are stored with their status, but still fail the command.
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        store_timings(year, &stored_timings, timings, &summary.reports);
+    }
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+    if has_failures {
+        process::exit(1);
+    }
+}
+
+/// Merges the new timings into the stored ones and updates the history and the readme.
+fn store_timings(year: Year, stored_timings: &Timings, timings: Timings, reports: &[PartReport]) {
+    let merged_timings = stored_timings.merge(&timings);
+    merged_timings.store_file(year).unwrap();
+
+    if let Err(e) = History::append(year, &history::records_from_reports(reports)) {
+        eprintln!("Failed to append to the timing history: {e}");
+    }
+
+    println!();
+
+    // the readme shows the benchmarks of the year in the flat layout only.
+    if Layout::of(year) == Layout::Yearly {
+        println!("Stored updated benchmarks of {year}.");
+        return;
+    }
+
+    match readme_benchmarks::update(merged_timings) {
+        Ok(()) => {
+            println!("Stored updated benchmarks.");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated benchmarks.");
         }
     }
 }
+
+/// Prints a summary of the comparison, returns whether any part got slower.
+fn report_regressions(diffs: &[compare::PartDiff]) -> bool {
+    let count = |change| diffs.iter().filter(|d| d.change == change).count();
+    let (slower, faster) = (count(Change::Slower), count(Change::Faster));
+
+    println!();
+    println!("{slower} slower, {faster} faster than the stored benchmarks.");
+
+    slower > 0
+}
+
+/// Shows how the benchmarks of a day changed over all stored runs.
+pub fn handle_history(year: Year, day: Day) {
+    history::print_day(&History::read_from_file(year), day);
+}
@@ -0,0 +1,204 @@
+/// Compares fresh benchmarks with the stored baseline, for `cargo time --compare`.
+///
+/// A part counts as slower or faster when the difference of the medians exceeds the 95% range of
+/// the difference of two single samples, `1.96 * sqrt(σ_baseline² + σ_current²)`. Noisy parts thus
+/// need a larger change to be flagged than stable ones.
+use std::time::Duration;
+
+use crate::template::report::PARSE_PART;
+use crate::template::runner::Z_95;
+use crate::template::timings::{PartTiming, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Slower,
+    Faster,
+    /// The difference is within the noise of the measurements.
+    Unchanged,
+    /// There is no stored timing with a standard deviation to compare with.
+    NoBaseline,
+}
+
+/// Comparison of a single benched part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDiff {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Option<PartTiming>,
+    pub current: PartTiming,
+    pub change: Change,
+}
+
+impl PartDiff {
+    /// Relative change of the median in percent.
+    pub fn percent(&self) -> Option<f64> {
+        let baseline = self.baseline?;
+        (baseline.nanos > 0.0)
+            .then(|| (self.current.nanos - baseline.nanos) / baseline.nanos * 100.0)
+    }
+}
+
+/// Classifies the change of a part between two measurements.
+/// Timings without a standard deviation have no baseline to compare with.
+pub fn classify(baseline: &PartTiming, current: &PartTiming) -> Change {
+    let (Some(baseline_stddev), Some(current_stddev)) =
+        (baseline.stddev_nanos, current.stddev_nanos)
+    else {
+        return Change::NoBaseline;
+    };
+
+    let delta = current.nanos - baseline.nanos;
+    let noise = Z_95 * (baseline_stddev.powi(2) + current_stddev.powi(2)).sqrt();
+
+    if delta.abs() <= noise {
+        Change::Unchanged
+    } else if delta > 0.0 {
+        Change::Slower
+    } else {
+        Change::Faster
+    }
+}
+
+/// Compares every part benched in `current` with the same part in `baseline`.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartDiff> {
+    let mut diffs = vec![];
+
+    for timing in &current.data {
+        let stored = baseline.data.iter().find(|t| t.day == timing.day);
+
+        for (part, current, baseline) in [
+            (PARSE_PART, timing.parse, stored.and_then(|t| t.parse)),
+            (1, timing.part_1, stored.and_then(|t| t.part_1)),
+            (2, timing.part_2, stored.and_then(|t| t.part_2)),
+        ] {
+            let Some(current) = current else {
+                continue;
+            };
+
+            diffs.push(PartDiff {
+                day: timing.day,
+                part,
+                baseline,
+                current,
+                change: baseline.map_or(Change::NoBaseline, |b| classify(&b, &current)),
+            });
+        }
+    }
+
+    diffs
+}
+
+pub fn print_diffs(diffs: &[PartDiff]) {
+    println!();
+    println!(
+        "{ANSI_BOLD}{:<6}  {:<5}  {:>20}  {:>20}  {:>8}  Change{ANSI_RESET}",
+        "Day", "Part", "Baseline", "Current", "Diff"
+    );
+
+    for diff in diffs {
+        println!(
+            "{:<6}  {:<5}  {:>20}  {:>20}  {:>8}  {}",
+            diff.day.to_string(),
+            match diff.part {
+                PARSE_PART => "parse".into(),
+                part => part.to_string(),
+            },
+            diff.baseline
+                .map_or_else(|| "-".into(), |b| format_stats(&b)),
+            format_stats(&diff.current),
+            diff.percent()
+                .map_or_else(String::new, |p| format!("{p:+.1}%")),
+            match diff.change {
+                Change::Slower => "slower",
+                Change::Faster => "faster",
+                Change::Unchanged => "",
+                Change::NoBaseline => "no baseline",
+            }
+        );
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_stats(timing: &PartTiming) -> String {
+    let median = timing.duration();
+    match timing.stddev_nanos {
+        Some(stddev) => {
+            let stddev = Duration::from_nanos(stddev as u64);
+            format!("{median:.1?} ± {stddev:.1?}")
+        }
+        None => format!("{median:.1?}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{classify, compare, Change};
+    use crate::day;
+    use crate::template::timings::{DayStatus, PartTiming, Timing, Timings};
+
+    fn stats(nanos: f64, stddev_nanos: f64) -> PartTiming {
+        PartTiming {
+            nanos,
+            stddev_nanos: Some(stddev_nanos),
+            samples: Some(100),
+            allocations: None,
+        }
+    }
+
+    fn timings(part_1: Option<PartTiming>, part_2: Option<PartTiming>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                status: DayStatus::Solved,
+                parse: None,
+                part_1,
+                part_2,
+            }],
+        }
+    }
+
+    #[test]
+    fn classifies_by_spread() {
+        assert_eq!(
+            classify(&stats(100.0, 5.0), &stats(110.0, 5.0)),
+            Change::Unchanged
+        );
+        assert_eq!(
+            classify(&stats(100.0, 1.0), &stats(110.0, 1.0)),
+            Change::Slower
+        );
+        assert_eq!(
+            classify(&stats(100.0, 1.0), &stats(90.0, 1.0)),
+            Change::Faster
+        );
+        assert_eq!(
+            classify(&stats(100.0, 0.0), &stats(100.0, 0.0)),
+            Change::Unchanged
+        );
+
+        let migrated = PartTiming {
+            nanos: 100.0,
+            stddev_nanos: None,
+            samples: None,
+            allocations: None,
+        };
+        assert_eq!(classify(&migrated, &stats(200.0, 1.0)), Change::NoBaseline);
+    }
+
+    #[test]
+    fn compares_benched_parts() {
+        let baseline = timings(Some(stats(100.0, 1.0)), None);
+        let current = timings(Some(stats(200.0, 1.0)), Some(stats(50.0, 1.0)));
+
+        let diffs = compare(&baseline, &current);
+
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].change, Change::Slower);
+        assert_eq!(diffs[0].percent(), Some(100.0));
+        assert_eq!(diffs[1].change, Change::NoBaseline);
+        assert_eq!(diffs[1].percent(), None);
+    }
+}
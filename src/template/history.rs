@@ -0,0 +1,483 @@
+/// Append-only history of benchmark runs, stored as one JSON record per line in
+/// `data/timing_history.jsonl`, or `data/{year}/timing_history.jsonl` for years in their own directories.
+/// Unlike `timings.json`, records are never rewritten, so it shows how solutions got faster or slower.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::report::{PartReport, Status, PARSE_PART};
+use crate::template::{data_dir, root_dir, Day, Year};
+
+static HISTORY_FILE_NAME: &str = "timing_history.jsonl";
+
+/// Environment variable that names the machine in recorded runs. Defaults to the hostname.
+pub const MACHINE_ENV: &str = "AOC_MACHINE";
+
+/// Benchmark times of a single day in a single run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryRecord {
+    pub day: Day,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Commit that was checked out, with a `-dirty` suffix if there were uncommitted changes.
+    pub commit: Option<String>,
+    pub machine: String,
+    /// The shared parse step, if the solution has one.
+    pub parse: Option<u64>,
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl HistoryRecord {
+    /// Total nanos of all benched parts, including the parse step.
+    pub fn total_nanos(&self) -> u64 {
+        self.parse.unwrap_or(0) + self.part_1.unwrap_or(0) + self.part_2.unwrap_or(0)
+    }
+}
+
+/// All recorded runs of a year, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub records: Vec<HistoryRecord>,
+}
+
+impl History {
+    /// Rehydrate the history of a year. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = match fs::read_to_string(get_path(year)) {
+            Ok(s) => s,
+            Err(_) => return History::default(),
+        };
+
+        match History::from_str(&s) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("{e}");
+                History::default()
+            }
+        }
+    }
+
+    /// Append the records of a run to the history of a year.
+    pub fn append(year: Year, records: &[HistoryRecord]) -> Result<(), io::Error> {
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(get_path(year))?;
+
+        for record in records {
+            let line = JsonValue::from(record)
+                .stringify()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            writeln!(file, "{line}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Records of a single day, oldest first.
+    pub fn of_day(&self, day: Day) -> impl Iterator<Item = &HistoryRecord> {
+        self.records.iter().filter(move |r| r.day == day)
+    }
+}
+
+/// Creates a record per day from the reports of a timed run, stamped with the current time,
+/// commit and machine.
+pub fn records_from_reports(reports: &[PartReport]) -> Vec<HistoryRecord> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let commit = current_commit();
+    let machine = current_machine();
+
+    let mut records: Vec<HistoryRecord> = vec![];
+
+    for report in reports
+        .iter()
+        .filter(|r| matches!(r.status, Status::Solved | Status::Parsed))
+    {
+        let index = match records.iter().position(|r| r.day == report.day) {
+            Some(index) => index,
+            None => {
+                records.push(HistoryRecord {
+                    day: report.day,
+                    timestamp,
+                    commit: commit.clone(),
+                    machine: machine.clone(),
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                });
+                records.len() - 1
+            }
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = Some(report.nanos as u64);
+
+        match report.part {
+            PARSE_PART => records[index].parse = nanos,
+            1 => records[index].part_1 = nanos,
+            2 => records[index].part_2 = nanos,
+            _ => {}
+        }
+    }
+
+    records.sort_unstable_by_key(|r| r.day);
+    records
+}
+
+/// Commit of the repository whose timings are stored, whatever the working directory.
+fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(root_dir())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let hash = git(&["rev-parse", "--short=12", "HEAD"])?;
+    let is_dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+fn current_machine() -> String {
+    env::var(MACHINE_ENV)
+        .ok()
+        .or_else(|| {
+            let output = Command::new("hostname").output().ok()?;
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .filter(|machine| !machine.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn get_path(year: Year) -> PathBuf {
+    data_dir(year).join(HISTORY_FILE_NAME)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints how the timing of a day changed over all recorded runs.
+/// Changes are relative to the previous run on the same machine.
+pub fn print_day(history: &History, day: Day) {
+    let records: Vec<&HistoryRecord> = history.of_day(day).collect();
+
+    println!("Day {day}");
+    println!("------");
+
+    if records.is_empty() {
+        println!("No recorded runs. Use `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    // like the readme table, only days with a parse step get a column for it.
+    let has_parse = records.iter().any(|r| r.parse.is_some());
+    let parse_column = |value: &str| {
+        if has_parse {
+            format!("{value:>10}  ")
+        } else {
+            String::new()
+        }
+    };
+
+    println!(
+        "{:<16}  {:<18}  {:<12}  {}{:>10}  {:>10}  {:>10}  {:>8}",
+        "Date (UTC)",
+        "Commit",
+        "Machine",
+        parse_column("Parse"),
+        "Part 1",
+        "Part 2",
+        "Total",
+        "Change"
+    );
+
+    let mut previous: HashMap<&str, u64> = HashMap::new();
+
+    for record in records {
+        let change = previous
+            .insert(&record.machine, record.total_nanos())
+            .map_or_else(String::new, |before| {
+                format_change(before, record.total_nanos())
+            });
+
+        println!(
+            "{:<16}  {:<18}  {:<12}  {}{:>10}  {:>10}  {:>10}  {:>8}",
+            format_timestamp(record.timestamp),
+            record.commit.as_deref().unwrap_or("-"),
+            record.machine,
+            parse_column(&format_nanos(record.parse)),
+            format_nanos(record.part_1),
+            format_nanos(record.part_2),
+            format_nanos(Some(record.total_nanos())),
+            change
+        );
+    }
+}
+
+fn format_nanos(nanos: Option<u64>) -> String {
+    nanos.map_or_else(
+        || "-".into(),
+        |n| format!("{:.1?}", Duration::from_nanos(n)),
+    )
+}
+
+fn format_change(before: u64, after: u64) -> String {
+    if before == 0 {
+        return String::new();
+    }
+    let change = (after as f64 - before as f64) / before as f64 * 100.0;
+    format!("{change:+.1}%")
+}
+
+/// Formats seconds since the unix epoch as `YYYY-MM-DD HH:MM` in UTC.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // civil from days, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for History {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let records = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let json = JsonValue::from_str(line).map_err(|_| {
+                    format!("line {} of the timing history is not valid JSON.", i + 1)
+                })?;
+                HistoryRecord::try_from(&json)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { records })
+    }
+}
+
+impl From<&HistoryRecord> for JsonValue {
+    fn from(value: &HistoryRecord) -> Self {
+        let optional = |x: Option<u64>| x.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64));
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert("parse".into(), optional(value.parse));
+        map.insert("part_1".into(), optional(value.part_1));
+        map.insert("part_2".into(), optional(value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.commit to be null or string.")?;
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.machine to be a string.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    if v.is_null() {
+                        None
+                    } else {
+                        v.get::<f64>().map(|x| *x as u64)
+                    }
+                })
+                .ok_or(format!("Expected record.{key} to be null or number."))
+        };
+
+        // the parse step is optional, records of runs before it was timed do not have it.
+        let parse = match json.get("parse") {
+            Some(_) => part("parse")?,
+            None => None,
+        };
+
+        Ok(HistoryRecord {
+            day,
+            timestamp: timestamp as u64,
+            commit: commit.cloned(),
+            machine: machine.clone(),
+            parse,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{format_change, format_timestamp, records_from_reports, History, HistoryRecord};
+    use crate::day;
+    use crate::template::report::{PartReport, Status, PARSE_PART};
+
+    fn report(day: u8, part: u8, nanos: f64) -> PartReport {
+        PartReport {
+            day: crate::template::Day::new(day).unwrap(),
+            part,
+            status: Status::Solved,
+            answer: Some("42".into()),
+            nanos,
+            stddev_nanos: 0.0,
+            samples: 10,
+            allocations: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let record = HistoryRecord {
+            day: day!(5),
+            timestamp: 1_733_400_000,
+            commit: Some("0123456789ab".into()),
+            machine: "laptop".into(),
+            parse: Some(300),
+            part_1: Some(1500),
+            part_2: None,
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let history = History::from_str(&format!("{line}\n\n{line}\n")).unwrap();
+
+        assert_eq!(history.records, vec![record.clone(), record]);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let err = History::from_str("{\"day\":\"05\"}\nnot json").unwrap_err();
+        assert!(err.contains("record.timestamp"));
+        let err = History::from_str("not json").unwrap_err();
+        assert!(err.contains("line 1"));
+    }
+
+    #[test]
+    fn groups_reports_by_day() {
+        let mut unsolved = report(3, 2, 7.0);
+        unsolved.status = Status::Unsolved;
+
+        let records = records_from_reports(&[
+            report(3, 1, 10.0),
+            report(1, 1, 5.0),
+            report(1, 2, 6.0),
+            unsolved,
+        ]);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].day, day!(1));
+        assert_eq!((records[0].part_1, records[0].part_2), (Some(5), Some(6)));
+        assert_eq!(records[0].total_nanos(), 11);
+        assert_eq!((records[1].part_1, records[1].part_2), (Some(10), None));
+    }
+
+    #[test]
+    fn records_parse_steps() {
+        let mut parse = report(7, PARSE_PART, 4.0);
+        parse.status = Status::Parsed;
+        parse.answer = None;
+
+        let records = records_from_reports(&[parse, report(7, 1, 10.0), report(7, 2, 20.0)]);
+
+        assert_eq!(records[0].parse, Some(4));
+        assert_eq!(records[0].total_nanos(), 34);
+    }
+
+    #[test]
+    fn reads_records_without_parse_steps() {
+        let line = r#"{"day":"05","timestamp":1733400000,"commit":null,"machine":"laptop","part_1":1500,"part_2":null}"#;
+        let history = History::from_str(line).unwrap();
+
+        assert_eq!(history.records[0].parse, None);
+        assert_eq!(history.records[0].total_nanos(), 1500);
+    }
+
+    #[test]
+    fn formats_timestamps_and_changes() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_405_400), "2024-12-05 13:30");
+        assert_eq!(format_timestamp(1_709_208_000), "2024-02-29 12:00");
+        assert_eq!(format_change(200, 150), "-25.0%");
+        assert_eq!(format_change(100, 110), "+10.0%");
+    }
+}
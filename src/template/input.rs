@@ -0,0 +1,184 @@
+/// Loading of puzzle inputs and examples.
+/// Files in `data` are resolved against the repository root, so solutions can run from any directory.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{Day, PuzzleId};
+
+/// Root of the repository, the directory that contains `Cargo.toml` and `data`.
+pub fn root_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// A path in the repository relative to its root, to keep messages short.
+pub(crate) fn relative_to_root(path: &Path) -> &Path {
+    path.strip_prefix(root_dir()).unwrap_or(path)
+}
+
+/// Where a solution binary reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The input file of the puzzle in `data/inputs`.
+    Puzzle,
+    /// A file passed with `--input <path>`, relative to the working directory.
+    File(PathBuf),
+    /// Standard input, passed as `--input -` or `-`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse the command-line arguments of a solution binary.
+    /// A trailing `--input` without a path is an error, rather than a run on the puzzle input.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let path = match args.iter().position(|x| x == "--input") {
+            Some(i) => Some(args.get(i + 1).ok_or_else(|| {
+                "`--input` expects a path, or `-` for standard input.".to_string()
+            })?),
+            None => args.iter().find(|x| *x == "-"),
+        };
+
+        Ok(match path.map(String::as_str) {
+            None => InputSource::Puzzle,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.into()),
+        })
+    }
+}
+
+/// An error which can be returned when loading an input.
+#[derive(Debug)]
+pub struct InputError {
+    /// The file that could not be read, `None` for standard input.
+    pub path: Option<PathBuf>,
+    pub source: io::Error,
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "could not read \"{}\": {}", path.display(), self.source),
+            None => write!(f, "could not read standard input: {}", self.source),
+        }
+    }
+}
+
+/// Read the input of a solution binary from the source requested on the command line.
+pub fn read_input(puzzle: PuzzleId) -> Result<String, InputError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let source = InputSource::from_args(&args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+
+    match source {
+        InputSource::Puzzle => try_read_puzzle_file("inputs", puzzle),
+        InputSource::File(path) => read_path(&path),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|source| InputError { path: None, source })?;
+            Ok(input)
+        }
+    }
+}
+
+/// Reads `data/{folder}/{day}.txt` of the flat layout.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    read_path(
+        &root_dir()
+            .join("data")
+            .join(folder)
+            .join(format!("{day}.txt")),
+    )
+}
+
+/// Reads `data/{folder}/{day}-{part}.txt` of the flat layout.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
+    read_path(
+        &root_dir()
+            .join("data")
+            .join(folder)
+            .join(format!("{day}-{part}.txt")),
+    )
+}
+
+/// Reads the text file of a puzzle in one of the data folders, following the layout of its year.
+pub fn try_read_puzzle_file(folder: &str, puzzle: PuzzleId) -> Result<String, InputError> {
+    read_path(&puzzle.data_path(folder, "txt"))
+}
+
+/// Reads the text file of a part of a puzzle, e.g. `data/examples/05-2.txt`, following the layout of its year.
+pub fn try_read_puzzle_file_part(
+    folder: &str,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Result<String, InputError> {
+    read_path(&puzzle.part_data_path(folder, part, "txt"))
+}
+
+fn read_path(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError {
+        path: Some(path.into()),
+        source,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{try_read_file, InputSource};
+    use crate::day;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(InputSource::from_args(&args(&[])), Ok(InputSource::Puzzle));
+        assert_eq!(
+            InputSource::from_args(&args(&["--time"])),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["--input", "other/05.txt"])),
+            Ok(InputSource::File("other/05.txt".into()))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["--input", "-"])),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["-"])),
+            Ok(InputSource::Stdin)
+        );
+    }
+
+    #[test]
+    fn rejects_input_without_path() {
+        assert!(InputSource::from_args(&args(&["--input"])).is_err());
+        assert!(InputSource::from_args(&args(&["--time", "--input"])).is_err());
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let err = try_read_file("nonexistent", day!(1)).unwrap_err();
+        assert!(err.to_string().contains("nonexistent/01.txt"));
+        assert!(try_read_file("examples", day!(1)).is_ok());
+    }
+}
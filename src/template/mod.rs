@@ -1,68 +1,174 @@
-use std::{env, fs};
-
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use input::*;
+pub use params::*;
+pub use puzzle_id::*;
+pub use year::*;
 
+mod allocations;
+mod answers;
+mod benchmark_chart;
+mod compare;
 mod day;
+mod history;
+mod input;
+mod params;
+mod puzzle_examples;
+mod puzzle_id;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod scaffold_template;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// If the file can not be read, use [`try_read_file`] to handle the error instead.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Helper function that reads a text file of a puzzle to a string, following the layout of its year.
+///
+/// # Panics
+/// If the file can not be read, use [`try_read_puzzle_file`] to handle the error instead.
+#[must_use]
+pub fn read_puzzle_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_puzzle_file(folder, puzzle).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Helper function that reads a text file of a puzzle to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// If the file can not be read, use [`try_read_puzzle_file_part`] to handle the error instead.
+#[must_use]
+pub fn read_puzzle_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    try_read_puzzle_file_part(folder, puzzle, part).unwrap_or_else(|err| panic!("{err}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// If the file can not be read, use [`try_read_file_part`] to handle the error instead.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file_part(folder, day, part).unwrap_or_else(|err| panic!("{err}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY`, `YEAR` and `PUZZLE` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The binary reads its input from `data/inputs`, from another file with `--input <path>`, or from stdin with `-`.
+/// Solutions of another year than `AOC_YEAR` name their year, e.g. `solution!(5, year = 2023)`.
+/// Solutions that parse the input once pass their parse function, e.g. `solution!(5, parse = parse_input)`.
+/// Their parts then take a reference to the parsed input, and the parse step is timed on its own.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, $crate::solution!(@year), [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, $crate::solution!(@year), [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, $crate::solution!(@year), [], [part_two, 2]);
+    };
+    ($day:expr, year = $year:expr) => {
+        $crate::solution!(@impl $day, $crate::year!($year), [], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, year = $year:expr) => {
+        $crate::solution!(@impl $day, $crate::year!($year), [], [part_one, 1]);
+    };
+    ($day:expr, 2, year = $year:expr) => {
+        $crate::solution!(@impl $day, $crate::year!($year), [], [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, $crate::solution!(@year), [$parse], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, $crate::solution!(@year), [$parse], [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, $crate::solution!(@year), [$parse], [part_two, 2]);
+    };
+    ($day:expr, year = $year:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, $crate::year!($year), [$parse], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, year = $year:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, $crate::year!($year), [$parse], [part_one, 1]);
+    };
+    ($day:expr, 2, year = $year:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, $crate::year!($year), [$parse], [part_two, 2]);
+    };
+
+    (@year) => {
+        $crate::template::Year::__parse_const(env!(
+            "AOC_YEAR",
+            "AOC_YEAR must be set to the year of the solutions, see `.cargo/config.toml`."
+        ))
+    };
+
+    // without a parse function, every part gets the raw input.
+    (@run $input:ident, [], $( [$func:expr, $part:expr] )*) => {
+        $( run_part($func, $input, PUZZLE, $part); )*
+    };
+    (@run $input:ident, [$parse:expr], $( [$func:expr, $part:expr] )*) => {
+        let parsed = run_parse($parse, $input, PUZZLE);
+        // calling the part in a closure lets e.g. a parsed `Vec<T>` deref to the `&[T]` of the part.
+        $( run_part(|parsed| $func(parsed), &parsed, PUZZLE, $part); )*
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $year:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current day.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = $year;
+
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
+        /// Runs all parts of the solution against `input`.
+        fn run_parts(input: &str) {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::solution!(@run input, [$($parse)?], $( [$func, $part] )*);
+        }
+
+        // Not part of the public API, used by the in-process registry.
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __solution() -> $crate::template::registry::Solution {
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                run: run_parts,
+            }
+        }
+
+        fn main() {
+            match $crate::template::read_input(PUZZLE) {
+                Ok(input) => run_parts(&input),
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+            }
         }
     };
 }
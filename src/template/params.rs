@@ -0,0 +1,101 @@
+/// Parameters of a solution binary, to run a puzzle with other constants than its own.
+/// They are passed as `--param <name>=<value>`, e.g. `cargo solve 11 --param blinks=1000`.
+use std::{env, fmt::Display, str::FromStr};
+
+/// The parameters passed to a solution binary, in order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Parse the command-line arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Self {
+        let params = args
+            .windows(2)
+            .filter(|pair| pair[0] == "--param")
+            .filter_map(|pair| parse_param(&pair[1]).ok())
+            .collect();
+
+        Params(params)
+    }
+
+    /// The value of a parameter, the last one wins if it was passed several times.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Parses a `<name>=<value>` parameter.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!(
+            "expected a parameter as `<name>=<value>`, got `{s}`."
+        )),
+    }
+}
+
+/// The value of the parameter `name` passed to this binary, `None` if it was not passed.
+///
+/// # Panics
+/// If the value can not be parsed as a `T`.
+#[must_use]
+pub fn param<T: FromStr>(name: &str) -> Option<T>
+where
+    T::Err: Display,
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    let value = Params::from_args(&args).get(name)?.to_string();
+
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(err) => panic!("invalid value `{value}` for parameter `{name}`: {err}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_param, Params};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_params() {
+        let params = Params::from_args(&args(&[
+            "--time",
+            "--param",
+            "blinks=1000",
+            "--param",
+            "size=7",
+            "--param",
+            "blinks=40",
+        ]));
+        assert_eq!(params.get("blinks"), Some("40"));
+        assert_eq!(params.get("size"), Some("7"));
+        assert_eq!(params.get("other"), None);
+
+        assert!(Params::from_args(&args(&["--input", "-"])).is_empty());
+        assert!(Params::from_args(&args(&["--param"])).is_empty());
+    }
+
+    #[test]
+    fn rejects_params_without_name() {
+        assert_eq!(
+            parse_param("empty="),
+            Ok(("empty".to_string(), String::new()))
+        );
+        assert!(parse_param("blinks").is_err());
+        assert!(parse_param("=1000").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+/// Finds the examples of a puzzle in its description in `data/puzzles`, so `scaffold` can write the
+/// example files and the expected answers of the tests.
+///
+/// The description keeps the HTML of the website, with one `<article>` per part. The example of a
+/// part is the first `<pre><code>` block of its article, the expected answer is the last highlighted
+/// `<code><em>` of the article. Both are heuristics that hold for most puzzles, check the scaffolded
+/// files before relying on them.
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::template::aoc_client::decode_entities;
+
+/// Examples found in the description of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    /// Example input of part one.
+    pub input: Option<String>,
+    /// Example input of part two, only set if it differs from the one of part one.
+    pub part_two_input: Option<String>,
+    /// Expected answers of the examples of part one and two.
+    pub answers: [Option<String>; 2],
+}
+
+impl PuzzleExamples {
+    pub fn parse(markdown: &str) -> Self {
+        static ARTICLE: OnceLock<Regex> = OnceLock::new();
+
+        let articles: Vec<&str> = ARTICLE
+            .get_or_init(|| Regex::new(r"(?s)<article[^>]*>.*?</article>").unwrap())
+            .find_iter(markdown)
+            .map(|m| m.as_str())
+            .collect();
+
+        let part_one = articles.first().copied().unwrap_or(markdown);
+        let part_two = articles.get(1).copied();
+
+        let input = first_code_block(part_one);
+        let part_two_input = part_two
+            .and_then(first_code_block)
+            .filter(|block| Some(block) != input.as_ref());
+
+        PuzzleExamples {
+            input,
+            part_two_input,
+            answers: [last_answer(part_one), part_two.and_then(last_answer)],
+        }
+    }
+}
+
+fn first_code_block(article: &str) -> Option<String> {
+    static CODE_BLOCK: OnceLock<Regex> = OnceLock::new();
+
+    CODE_BLOCK
+        .get_or_init(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap())
+        .captures(article)
+        .map(|c| strip_tags(&c[1]))
+        .filter(|block| !block.trim().is_empty())
+}
+
+fn last_answer(article: &str) -> Option<String> {
+    static ANSWER: OnceLock<Regex> = OnceLock::new();
+
+    ANSWER
+        .get_or_init(|| {
+            Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap()
+        })
+        .captures_iter(article)
+        .last()
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| strip_tags(m.as_str()).trim().to_string())
+        .filter(|answer| !answer.is_empty())
+}
+
+/// Examples highlight parts of their text with tags like `<em>`, which are not part of the input.
+fn strip_tags(html: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+
+    let text = TAG
+        .get_or_init(|| Regex::new(r"<[^>]+>").unwrap())
+        .replace_all(html, "");
+
+    decode_entities(&text)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleExamples;
+
+    const PART_ONE: &str = "<article class=\"day-desc\"><h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p><pre><code>3   4\n4   3\n<em>2</em>   5\n</code></pre><p>Pairs like <code>3 &lt; 4</code> add up to <code><em>11</em></code>.</p></article>";
+
+    #[test]
+    fn parses_part_one() {
+        let examples = PuzzleExamples::parse(PART_ONE);
+        assert_eq!(examples.input, Some("3   4\n4   3\n2   5\n".into()));
+        assert_eq!(examples.part_two_input, None);
+        assert_eq!(examples.answers, [Some("11".into()), None]);
+    }
+
+    #[test]
+    fn parses_part_two_with_the_same_example() {
+        let markdown = format!(
+            "{PART_ONE}\n\n<article class=\"day-desc\"><h2>--- Part Two ---</h2><p>Again:</p><pre><code>3   4\n4   3\n2   5\n</code></pre><p>The score is <em><code>31</code></em>.</p></article>"
+        );
+        let examples = PuzzleExamples::parse(&markdown);
+        assert_eq!(examples.part_two_input, None);
+        assert_eq!(examples.answers, [Some("11".into()), Some("31".into())]);
+    }
+
+    #[test]
+    fn parses_part_two_with_its_own_example() {
+        let markdown = format!(
+            "{PART_ONE}\n\n<article class=\"day-desc\"><h2>--- Part Two ---</h2><pre><code>do()&amp;don't()\n</code></pre><p>Now it is <code><em>CMZ</em></code>.</p></article>"
+        );
+        let examples = PuzzleExamples::parse(&markdown);
+        assert_eq!(examples.part_two_input, Some("do()&don't()\n".into()));
+        assert_eq!(examples.answers[1], Some("CMZ".into()));
+    }
+
+    #[test]
+    fn handles_descriptions_without_examples() {
+        let examples = PuzzleExamples::parse("<article><p>Nothing to see.</p></article>");
+        assert_eq!(examples, PuzzleExamples::default());
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::template::{root_dir, Day, Year};
+
+/// Identifies a puzzle across all years of advent.
+///
+/// # Display
+/// This value displays as `{year}/{day}`.
+///
+/// ```
+/// # use advent_of_code::{day, year};
+/// # use advent_of_code::template::PuzzleId;
+/// let puzzle = PuzzleId::new(year!(2023), day!(5));
+/// assert_eq!(puzzle.to_string(), "2023/05")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+/// Directory layout of the files that belong to a year.
+///
+/// The configured year (`AOC_YEAR`) keeps the flat layout of the template, unless a `data/{year}`
+/// directory exists. Every other year lives in its own directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// `src/bin/{day}.rs` and `data/{folder}/{day}.txt`.
+    Flat,
+    /// `src/bin/{year}_{day}.rs` and `data/{year}/{folder}/{day}.txt`.
+    Yearly,
+}
+
+impl Layout {
+    pub fn of(year: Year) -> Self {
+        let yearly_dir = root_dir().join("data").join(year.to_string());
+        if Year::configured() == Some(year) && !yearly_dir.is_dir() {
+            Layout::Flat
+        } else {
+            Layout::Yearly
+        }
+    }
+}
+
+/// Directory that holds inputs, examples, answers and timings of a year, in the repository root.
+pub fn data_dir(year: Year) -> PathBuf {
+    match Layout::of(year) {
+        Layout::Flat => root_dir().join("data"),
+        Layout::Yearly => root_dir().join("data").join(year.to_string()),
+    }
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        PuzzleId { year, day }
+    }
+
+    pub fn layout(self) -> Layout {
+        Layout::of(self.year)
+    }
+
+    /// Name of the binary that holds the solution.
+    pub fn bin_name(self) -> String {
+        match self.layout() {
+            Layout::Flat => self.day.to_string(),
+            Layout::Yearly => format!("{}_{}", self.year, self.day),
+        }
+    }
+
+    /// Path of the solution module, e.g. `src/bin/05.rs`.
+    pub fn bin_path(self) -> PathBuf {
+        root_dir()
+            .join("src/bin")
+            .join(format!("{}.rs", self.bin_name()))
+    }
+
+    /// Path of a file of this puzzle in one of the data folders, e.g. `data/inputs/05.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+
+    /// Path of a file of one part of this puzzle, e.g. `data/examples/05-2.txt`.
+    pub fn part_data_path(self, folder: &str, part: u8, extension: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}-{part}.{extension}", self.day))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Layout, PuzzleId};
+    use crate::template::root_dir;
+    use crate::{day, year};
+
+    #[test]
+    fn places_other_years_in_their_own_directories() {
+        let puzzle = PuzzleId::new(year!(2015), day!(5));
+        assert_eq!(puzzle.layout(), Layout::Yearly);
+        assert_eq!(puzzle.bin_name(), "2015_05");
+        assert_eq!(puzzle.bin_path(), root_dir().join("src/bin/2015_05.rs"));
+        assert_eq!(
+            puzzle.data_path("inputs", "txt"),
+            root_dir().join("data/2015/inputs/05.txt")
+        );
+        assert_eq!(
+            puzzle.part_data_path("examples", 2, "txt"),
+            root_dir().join("data/2015/examples/05-2.txt")
+        );
+        assert_eq!(puzzle.to_string(), "2015/05");
+    }
+}
@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::allocations::{format_bytes, Allocations};
+use crate::template::benchmark_chart::{self, CHART_PATH};
+use crate::template::timings::{DayStatus, PartTiming, Timing, Timings};
+use crate::template::{root_dir, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -55,23 +57,63 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // allocation columns only show up once benchmarks were stored with `--allocations`.
+    let has_allocations = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_1, t.part_2])
+        .flatten()
+        .any(|part| part.allocations.is_some());
+
+    // the parse column only shows up for solutions that pass a `parse` function to `solution!`.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_allocations {
+        columns.extend(["Allocations", "Allocated", "Peak heap"]);
+    }
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("![Benchmark chart](./{CHART_PATH})"),
+        String::new(),
+        format!("| {} |", columns.join(" | ")),
+        format!("| {}  |", vec![":---:"; columns.len()].join(" | ")),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+        let mut line = format!("| [Day {}]({}) |", timing.day.into_inner(), path);
+
+        if has_parse {
+            line.push_str(&format!(
+                " `{}` |",
+                format_part(timing.parse, timing.status)
+            ));
+        }
+
+        line.push_str(&format!(
+            " `{}` | `{}` |",
+            format_part(timing.part_1, timing.status),
+            format_part(timing.part_2, timing.status)
         ));
+
+        if has_allocations {
+            line.push_str(&format!(
+                " `{}` | `{}` | `{}` |",
+                format_allocations(&timing, |a| a.count.to_string()),
+                format_allocations(&timing, |a| format_bytes(a.bytes)),
+                format_allocations(&timing, |a| format_bytes(a.peak_bytes))
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -81,6 +123,27 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+/// Parts without a time show why they have none if their day did not finish.
+fn format_part(part: Option<PartTiming>, status: DayStatus) -> String {
+    match (part, status) {
+        (Some(part), _) => format!("{:.1?}", part.duration()),
+        (None, DayStatus::Solved) => "-".into(),
+        (None, status) => status.to_string(),
+    }
+}
+
+/// Allocation numbers of both parts, e.g. `12 / 40`.
+fn format_allocations(timing: &Timing, field: impl Fn(Allocations) -> String) -> String {
+    [timing.part_1, timing.part_2]
+        .iter()
+        .map(|part| {
+            part.and_then(|part| part.allocations)
+                .map_or_else(|| "-".into(), &field)
+        })
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -89,8 +152,10 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    benchmark_chart::store(&timings)?;
+
+    let path = root_dir().join("README.md");
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
     fs::write(path, &readme)?;
@@ -100,28 +165,53 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::{
+            allocations::Allocations,
+            timings::{DayStatus, PartTiming, Timing, Timings},
+        },
+    };
+
+    fn millis(millis: u32) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos: f64::from(millis) * 1e6,
+            stddev_nanos: None,
+            samples: None,
+            allocations: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: millis(10),
+                    part_2: millis(20),
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: millis(30),
+                    part_2: millis(40),
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: millis(40),
+                    part_2: millis(50),
+                },
+                Timing {
+                    day: day!(5),
+                    status: DayStatus::TimedOut,
+                    parse: None,
+                    part_1: millis(5),
+                    part_2: None,
                 },
             ],
         }
@@ -167,11 +257,14 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
+            "| [Day 5](./src/bin/05.rs) | `5.0ms` | `timed out` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +273,42 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_parse_steps() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = millis(3);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 193.0).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `3.0ms` | `30.0ms` | `40.0ms` |"));
+        assert!(s.contains("| [Day 5](./src/bin/05.rs) | `timed out` | `5.0ms` | `timed out` |"));
+    }
+
+    #[test]
+    fn format_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1 = timings.data[0].part_1.map(|part| PartTiming {
+            allocations: Some(Allocations {
+                count: 12,
+                bytes: 2048,
+                peak_bytes: 512,
+            }),
+            ..part
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Allocations | Allocated | Peak heap |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `12 / -` | `2.0KiB / -` | `512B / -` |"
+        ));
+        assert!(s.contains(
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `- / -` | `- / -` | `- / -` |"
+        ));
+    }
 }
@@ -0,0 +1,71 @@
+/// In-process solution registry.
+/// `build.rs` compiles every scaffolded solution into the `all` binary, which runs the requested days
+/// one after another without spawning a `cargo` process per day.
+use std::{env, panic, process};
+
+use crate::template::{run_multi::print_day_header, try_read_puzzle_file, Day, PuzzleId, Year};
+
+/// A solution that can be run in-process. Created by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs all parts of the solution against the given input.
+    pub run: fn(&str),
+}
+
+/// Includes the registry generated by `build.rs`, exposing `solutions::all()`.
+#[macro_export]
+macro_rules! registry {
+    () => {
+        include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+    };
+}
+
+/// Entry point of the `all` binary. Runs every day passed as a command-line argument.
+/// The year is passed with `--year`, and defaults to `AOC_YEAR`.
+///
+/// A panicking solution is reported and does not abort the remaining days, but the process exits
+/// with an error once all days ran.
+pub fn run(solutions: &[Solution]) {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let year = args
+        .iter()
+        .position(|x| x == "--year")
+        .and_then(|i| args.get(i + 1)?.parse().ok())
+        .or_else(Year::configured)
+        .expect("expected a year, either with `--year` or through AOC_YEAR.");
+
+    let days: Vec<Day> = args.iter().filter_map(|x| x.parse().ok()).collect();
+
+    let mut need_space = false;
+    let mut has_failures = false;
+
+    for day in days {
+        print_day_header(day, &mut need_space);
+
+        let puzzle = PuzzleId::new(year, day);
+        let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+            println!("Not solved.");
+            continue;
+        };
+
+        let input = match try_read_puzzle_file("inputs", puzzle) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("{err}");
+                has_failures = true;
+                continue;
+            }
+        };
+
+        // the failing part already reported its panic message, `run_multi` prints it.
+        if panic::catch_unwind(|| (solution.run)(&input)).is_err() {
+            has_failures = true;
+        }
+    }
+
+    if has_failures {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,364 @@
+/// Machine-readable protocol between solution binaries and the `all` / `time` commands.
+/// Every part run by the `solution!` macro appends one JSON record per line to the file named by
+/// the `AOC_REPORT_FILE` environment variable, which keeps the records apart from terminal output.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    allocations::{self, Allocations},
+    runner::Measurement,
+    Day,
+};
+
+/// Environment variable that holds the path of the report file.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// Version of the record format. Bump when making incompatible changes to [`PartReport`].
+pub const PROTOCOL_VERSION: u8 = 6;
+
+/// Part number of the records of the shared parse step of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// Outcome of running a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The shared parse step ran, it has no answer of its own.
+    Parsed,
+    /// The part or the parse step panicked, see [`PartReport::error`].
+    Failed,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Parsed => "parsed",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+/// Result and timing of a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Median of the samples.
+    pub nanos: f64,
+    /// Standard deviation of the samples, zero for a single sample.
+    pub stddev_nanos: f64,
+    pub samples: u128,
+    /// Heap usage of the part, only counted with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+    /// Panic message of a part that failed.
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    pub fn new(day: Day, part: u8, answer: Option<String>, measurement: &Measurement) -> Self {
+        PartReport {
+            day,
+            part,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            },
+            answer,
+            nanos: measurement.median.as_nanos() as f64,
+            stddev_nanos: measurement.stddev.as_nanos() as f64,
+            samples: measurement.samples,
+            allocations: measurement.allocations,
+            error: None,
+        }
+    }
+
+    /// Record of the shared parse step, see [`PARSE_PART`].
+    pub fn parse(day: Day, measurement: &Measurement) -> Self {
+        PartReport {
+            status: Status::Parsed,
+            ..PartReport::new(day, PARSE_PART, None, measurement)
+        }
+    }
+
+    /// Record of a part that panicked, it has neither an answer nor a timing.
+    pub fn failed(day: Day, part: u8, error: String) -> Self {
+        PartReport {
+            day,
+            part,
+            status: Status::Failed,
+            answer: None,
+            nanos: 0.0,
+            stddev_nanos: 0.0,
+            samples: 0,
+            allocations: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Append a record to the report file if the parent process requested one.
+pub fn append(report: &PartReport) -> Result<(), io::Error> {
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = JsonValue::from(report)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read all records from a report file. A missing file yields no records.
+pub fn read_file(path: &Path) -> Result<Vec<PartReport>, String> {
+    match fs::read_to_string(path) {
+        Ok(s) => parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse newline-delimited report records.
+pub fn parse(s: &str) -> Result<Vec<PartReport>, String> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("report record is not valid JSON."))?;
+            PartReport::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(PROTOCOL_VERSION)),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "allocations".into(),
+            value.allocations.map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let version = json
+            .get("version")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.version to be a number.")?;
+
+        if version != f64::from(PROTOCOL_VERSION) {
+            return Err(format!(
+                "Unsupported report version {version}, expected {PROTOCOL_VERSION}."
+            ));
+        }
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|p| *p == f64::from(PARSE_PART) || *p == 1.0 || *p == 2.0)
+            .ok_or("Expected report.part to be 0, 1 or 2.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .and_then(|s| {
+                [
+                    Status::Solved,
+                    Status::Unsolved,
+                    Status::Parsed,
+                    Status::Failed,
+                ]
+                .into_iter()
+                .find(|status| status.as_str() == s)
+            })
+            .ok_or("Expected report.status to be `solved`, `unsolved`, `parsed` or `failed`.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        let stddev_nanos = json
+            .get("stddev_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.stddev_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let allocations = allocations::optional_from_json(json, "allocations")?;
+
+        let error = match json.get("error") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected report.error to be null or string.")?,
+            ),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartReport {
+            day,
+            part: part as u8,
+            status,
+            answer: answer.cloned(),
+            nanos,
+            stddev_nanos,
+            samples: samples as u128,
+            allocations,
+            error: error.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, PartReport, Status, PARSE_PART};
+    use crate::day;
+    use crate::template::allocations::Allocations;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_reports() {
+        let reports = vec![
+            PartReport {
+                day: day!(1),
+                part: PARSE_PART,
+                status: Status::Parsed,
+                answer: None,
+                nanos: 12.0,
+                stddev_nanos: 0.5,
+                samples: 100,
+                allocations: None,
+                error: None,
+            },
+            PartReport {
+                day: day!(1),
+                part: 1,
+                status: Status::Solved,
+                answer: Some("line 1\nline 2 (74.13ns @ 100 samples)".into()),
+                nanos: 74.0,
+                stddev_nanos: 2.5,
+                samples: 100,
+                allocations: Some(Allocations {
+                    count: 2,
+                    bytes: 64,
+                    peak_bytes: 32,
+                }),
+                error: None,
+            },
+            PartReport {
+                day: day!(1),
+                part: 2,
+                status: Status::Unsolved,
+                answer: None,
+                nanos: 1.0,
+                stddev_nanos: 0.0,
+                samples: 1,
+                allocations: None,
+                error: None,
+            },
+            PartReport::failed(day!(1), 2, "attempt to subtract with overflow".into()),
+        ];
+
+        let s = reports
+            .iter()
+            .map(|r| JsonValue::from(r).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(s.lines().count(), 4);
+        assert_eq!(parse(&s).unwrap(), reports);
+    }
+
+    #[test]
+    fn ignores_blank_lines() {
+        let s = "\n{\"version\":6,\"day\":\"05\",\"part\":1,\"status\":\"solved\",\"answer\":\"42\",\"nanos\":5,\"stddev_nanos\":1,\"samples\":10}\n\n";
+        let reports = parse(s).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].day, day!(5));
+        assert_eq!(reports[0].answer, Some("42".into()));
+        assert_eq!(reports[0].samples, 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_outdated_version() {
+        let s = r#"{"version":3,"day":"01","part":1,"status":"solved","answer":"42","nanos":5,"samples":10}"#;
+        parse(s).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_stray_output() {
+        parse("Part 1: 0 (74.13ns @ 100000 samples)").unwrap();
+    }
+}
@@ -1,41 +1,200 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    answers::{Answers, Verification},
+    report::{PartReport, Status, PARSE_PART},
+    timings::{DayStatus, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use child_commands::ChildRun;
 
-    let mut need_space = false;
+/// Name of the binary that runs all solutions in-process, see `build.rs`.
+const IN_PROCESS_BIN: &str = "all";
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// A solution part whose answer differs from the stored answer.
+#[derive(Clone, Debug)]
+pub struct Mismatch {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub expected: String,
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Outcome of running a set of days.
+pub struct RunSummary {
+    /// Timings of all days, only present for timed runs.
+    pub timings: Option<Timings>,
+    pub mismatches: Vec<Mismatch>,
+    /// Reports of all parts that ran.
+    pub reports: Vec<PartReport>,
+    /// How the run of every requested day ended.
+    pub statuses: Vec<(Day, DayStatus)>,
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+impl RunSummary {
+    /// Whether any day panicked, exited with an error or timed out.
+    pub fn has_failures(&self) -> bool {
+        self.statuses
+            .iter()
+            .any(|(_, status)| matches!(status, DayStatus::Failed | DayStatus::TimedOut))
+    }
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+/// Outcome of running a single day.
+struct DayRun {
+    day: Day,
+    status: DayStatus,
+    /// Reports of the parts that finished, also for days that failed or timed out later on.
+    reports: Vec<PartReport>,
+}
+
+impl DayRun {
+    fn not_scaffolded(day: Day) -> Self {
+        DayRun {
+            day,
+            status: DayStatus::NotScaffolded,
+            reports: vec![],
+        }
+    }
+
+    /// Derives the status of a day from its child, printing why the day did not finish.
+    fn from_child(day: Day, run: Result<ChildRun, Error>) -> Self {
+        let (status, reports) = match run {
+            Ok(run) if run.reports.iter().any(|r| r.status == Status::Failed) => {
+                // parts that panicked report why, which says more than the exit status.
+                for failure in describe_failures(&run.reports) {
+                    eprintln!("Day {day} {failure}");
+                }
+                (DayStatus::Failed, run.reports)
+            }
+            Ok(run) => match run.exit_status {
+                Some(exit_status) if exit_status.success() => (DayStatus::Solved, run.reports),
+                Some(exit_status) => {
+                    eprintln!("Day {day} exited with {exit_status}.");
+                    (DayStatus::Failed, run.reports)
+                }
+                None => {
+                    eprintln!("Day {day} timed out and was killed.");
+                    (DayStatus::TimedOut, run.reports)
+                }
+            },
+            Err(e) => {
+                eprintln!("Failed to run day {day} ({e:?}).");
+                (DayStatus::Failed, vec![])
             }
-        });
+        };
+
+        DayRun {
+            day,
+            status,
+            reports,
+        }
+    }
+}
 
-    if is_timed {
-        let timings = Timings { data: timings };
+/// Describes the parts that panicked, e.g. `panicked in part 2: index out of bounds`.
+fn describe_failures(reports: &[PartReport]) -> Vec<String> {
+    reports
+        .iter()
+        .filter(|r| r.status == Status::Failed)
+        .map(|r| {
+            let step = if r.part == PARSE_PART {
+                "the parse step".to_string()
+            } else {
+                format!("part {}", r.part)
+            };
+            format!(
+                "panicked in {step}: {}",
+                r.error.as_deref().unwrap_or("no message")
+            )
+        })
+        .collect()
+}
+
+/// Runs the given days and collects their reports.
+///
+/// Every day runs in its own process, so a day that panics or exits with an error does not stop
+/// the others. With a `timeout`, a day is killed once one of its parts runs longer than that.
+///
+/// With `count_allocations`, the solutions are built with the counting allocator and report the
+/// heap usage of every part.
+///
+/// With more than one job, days run concurrently and their output is printed in day order once it
+/// is complete. Timed runs always run sequentially, so that concurrent days do not skew the
+/// benchmarks.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    count_allocations: bool,
+) -> RunSummary {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+    let jobs = if is_timed { 1 } else { jobs.max(1) };
+    let timer = Instant::now();
+
+    // build once up front and run the executable directly, so the days do not wait on cargo.
+    let executable = match child_commands::build_in_process(is_release, count_allocations) {
+        Ok(executable) => Some(executable),
+        Err(e) => {
+            eprintln!("Failed to build the solutions ({e:?}), running them one by one.");
+            None
+        }
+    };
+
+    let (runs, jobs) = match &executable {
+        Some(executable) if jobs > 1 => {
+            (run_parallel(year, &days, executable, jobs, timeout), jobs)
+        }
+        _ => (
+            run_sequential(
+                year,
+                &days,
+                executable.as_deref(),
+                is_release,
+                is_timed,
+                timeout,
+                count_allocations,
+            ),
+            1,
+        ),
+    };
+
+    print_summary(&runs, jobs, timer.elapsed());
+
+    let reports: Vec<PartReport> = runs
+        .iter()
+        .flat_map(|run| run.reports.iter().cloned())
+        .collect();
+
+    let mismatches = find_mismatches(year, &reports);
+    print_mismatches(&mismatches);
+
+    let timings = if is_timed {
+        // days without a solution have nothing to store, all others keep their status.
+        let timings = Timings {
+            data: runs
+                .iter()
+                .filter(|run| run.status != DayStatus::NotScaffolded)
+                .map(|run| child_commands::timing_from_reports(&run.reports, run.day, run.status))
+                .collect(),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -43,14 +202,236 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    RunSummary {
+        timings,
+        mismatches,
+        reports,
+        statuses: runs.iter().map(|run| (run.day, run.status)).collect(),
+    }
+}
+
+/// Runs the days one after another, printing their output as it happens.
+/// Without an executable, every day is run with `cargo run` instead.
+fn run_sequential(
+    year: Year,
+    days: &[Day],
+    executable: Option<&Path>,
+    is_release: bool,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    count_allocations: bool,
+) -> Vec<DayRun> {
+    let mut need_space = false;
+
+    days.iter()
+        .map(|&day| {
+            let puzzle = PuzzleId::new(year, day);
+
+            if !child_commands::is_scaffolded(puzzle) {
+                print_day_header(day, &mut need_space);
+                println!("Not solved.");
+                return DayRun::not_scaffolded(day);
+            }
+
+            let command = match executable {
+                Some(executable) => {
+                    // the in-process binary prints the heading of the day itself.
+                    if need_space {
+                        println!();
+                    }
+                    need_space = true;
+                    child_commands::in_process_command(executable, puzzle, is_timed)
+                }
+                None => {
+                    print_day_header(day, &mut need_space);
+                    child_commands::solution_command(
+                        puzzle,
+                        is_timed,
+                        is_release,
+                        count_allocations,
+                    )
+                }
+            };
+
+            DayRun::from_child(day, child_commands::run_day(command, day, timeout, false))
+        })
+        .collect()
+}
+
+/// Runs every day in its own process of the in-process binary, `jobs` at a time.
+fn run_parallel(
+    year: Year,
+    days: &[Day],
+    executable: &Path,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Vec<DayRun> {
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next_day = &next_day;
+
+            scope.spawn(move || loop {
+                let index = next_day.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+
+                let puzzle = PuzzleId::new(year, day);
+                let run = child_commands::is_scaffolded(puzzle).then(|| {
+                    let command = child_commands::in_process_command(executable, puzzle, false);
+                    child_commands::run_day(command, day, timeout, true)
+                });
+
+                if sender.send((index, run)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // print days in order, buffering the ones that finished early.
+        let mut pending = BTreeMap::new();
+        let mut runs = vec![];
+
+        for (index, run) in receiver {
+            pending.insert(index, run);
+
+            while let Some(run) = pending.remove(&runs.len()) {
+                runs.push(print_captured(days[runs.len()], run, !runs.is_empty()));
+            }
+        }
+
+        runs
+    })
+}
+
+/// Prints the output of a day that ran with captured output. `None` stands for a day without a
+/// solution.
+fn print_captured(day: Day, run: Option<Result<ChildRun, Error>>, mut need_space: bool) -> DayRun {
+    let Some(run) = run else {
+        print_day_header(day, &mut need_space);
+        println!("Not solved.");
+        return DayRun::not_scaffolded(day);
+    };
+
+    match &run {
+        Ok(run) => {
+            if need_space {
+                println!();
+            }
+            let _ = io::stdout().write_all(&run.stdout);
+            let _ = io::stderr().write_all(&run.stderr);
+        }
+        Err(_) => print_day_header(day, &mut need_space),
+    }
+
+    DayRun::from_child(day, run)
+}
+
+fn print_summary(runs: &[DayRun], jobs: usize, elapsed: Duration) {
+    let solved_parts = runs
+        .iter()
+        .flat_map(|run| &run.reports)
+        .filter(|r| r.status == Status::Solved)
+        .count();
+    let with_jobs = if jobs > 1 {
+        format!(" with {jobs} jobs")
+    } else {
+        String::new()
+    };
+
+    println!(
+        "\n{ANSI_BOLD}Summary:{ANSI_RESET} ran {} days{with_jobs} in {elapsed:.2?}, {solved_parts} parts solved.",
+        runs.len()
+    );
+
+    let days_with = |status| {
+        runs.iter()
+            .filter(|run| run.status == status)
+            .map(|run| run.day.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    println!(
+        "Days: {} solved, {} failed, {} timed out, {} not scaffolded.",
+        days_with(DayStatus::Solved).len(),
+        days_with(DayStatus::Failed).len(),
+        days_with(DayStatus::TimedOut).len(),
+        days_with(DayStatus::NotScaffolded).len()
+    );
+
+    for (label, status) in [
+        ("Failed days", DayStatus::Failed),
+        ("Timed out days", DayStatus::TimedOut),
+    ] {
+        let days = days_with(status);
+        if !days.is_empty() {
+            println!("{label}: {}", days.join(", "));
+        }
+    }
+}
+
+/// Compare reported answers with the answers stored in `data/answers`.
+fn find_mismatches(year: Year, reports: &[PartReport]) -> Vec<Mismatch> {
+    let mut answers: HashMap<Day, Answers> = HashMap::new();
+
+    reports
+        .iter()
+        .filter_map(|r| {
+            let answer = r.answer.as_ref()?;
+            let stored = answers
+                .entry(r.day)
+                .or_insert_with(|| Answers::read_from_file(PuzzleId::new(year, r.day)));
+
+            match stored.verify(r.part, answer) {
+                Verification::Incorrect { expected } => Some(Mismatch {
+                    day: r.day,
+                    part: r.part,
+                    answer: answer.clone(),
+                    expected,
+                }),
+                Verification::Correct | Verification::Unknown => None,
+            }
+        })
+        .collect()
+}
+
+fn print_mismatches(mismatches: &[Mismatch]) {
+    if mismatches.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Answer mismatches:{ANSI_RESET}");
+    for m in mismatches {
+        println!(
+            "✗ Day {} Part {}: got {}, expected {}",
+            m.day, m.part, m.answer, m.expected
+        );
     }
 }
 
+/// Print the heading of a day, separated from the previous day by an empty line.
+pub fn print_day_header(day: Day, need_space: &mut bool) {
+    if *need_space {
+        println!();
+    }
+    *need_space = true;
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
     IO(io::Error),
+    Report(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -59,136 +440,282 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, IN_PROCESS_BIN};
+    use crate::template::{
+        allocations,
+        report::{self, PartReport, Status, PARSE_PART, REPORT_FILE_ENV},
+        timings::{DayStatus, PartTiming},
+        Day, PuzzleId,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        collections::HashMap,
+        env, fs,
+        io::{self, Read},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
+        str::FromStr,
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// How often a child with a timeout is checked on.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Build the in-process binary without running it, and return the path of the executable.
+    pub fn build_in_process(is_release: bool, count_allocations: bool) -> Result<PathBuf, Error> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            IN_PROCESS_BIN,
+        ];
 
         if is_release {
             args.push("--release");
         }
 
+        if count_allocations {
+            args.extend(["--features", allocations::FEATURE]);
+        }
+
+        let output = Command::new("cargo")
+            .args(args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::IO(io::Error::other(format!(
+                "cargo build exited with {}",
+                output.status
+            ))));
+        }
+
+        // cargo prints one JSON message per line, the artifact of the binary names its executable.
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| JsonValue::from_str(line).ok())
+            .find_map(|message| {
+                let message = message.get::<HashMap<String, JsonValue>>()?;
+                let target = message.get("target")?.get::<HashMap<String, JsonValue>>()?;
+
+                if target.get("name")?.get::<String>()? != IN_PROCESS_BIN {
+                    return None;
+                }
+
+                message
+                    .get("executable")?
+                    .get::<String>()
+                    .map(PathBuf::from)
+            })
+            .ok_or_else(|| Error::Report("cargo did not report the executable.".into()))
+    }
+
+    /// Whether the solution bin of a puzzle exists.
+    pub fn is_scaffolded(puzzle: PuzzleId) -> bool {
+        puzzle.bin_path().exists()
+    }
+
+    /// Command that runs a single day with the built in-process binary.
+    pub fn in_process_command(executable: &Path, puzzle: PuzzleId, is_timed: bool) -> Command {
+        let mut command = Command::new(executable);
+
+        if is_timed {
+            command.arg("--time");
+        }
+
+        command.args(["--year", &puzzle.year.to_string(), &puzzle.day.to_string()]);
+        command
+    }
+
+    /// Command that runs the solution bin of a puzzle through `cargo run`.
+    pub fn solution_command(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        count_allocations: bool,
+    ) -> Command {
+        let mut command = Command::new("cargo");
+        command.args(["run", "--quiet", "--bin", &puzzle.bin_name()]);
+
+        if is_release {
+            command.arg("--release");
+        }
+
+        if count_allocations {
+            command.args(["--features", allocations::FEATURE]);
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            command.args(["--", "--time"]);
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        command
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    /// Outcome of a child that ran a single day.
+    pub struct ChildRun {
+        pub reports: Vec<PartReport>,
+        /// Exit status of the child, `None` if it was killed after timing out.
+        pub exit_status: Option<ExitStatus>,
+        /// Terminal output of the child, empty unless it was captured.
+        pub stdout: Vec<u8>,
+        pub stderr: Vec<u8>,
+    }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    /// Run a day and collect the reports of its parts. The child prints to the terminal directly,
+    /// unless `capture` is set.
+    ///
+    /// With a `timeout`, the child is killed once a part runs longer than that. Every reported part
+    /// restarts the clock, so the timeout applies to each part rather than to the whole day.
+    pub fn run_day(
+        mut command: Command,
+        day: Day,
+        timeout: Option<Duration>,
+        capture: bool,
+    ) -> Result<ChildRun, Error> {
+        let output = || {
+            if capture {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            }
+        };
+
+        let (reports, (exit_status, stdout, stderr)) =
+            with_report_file(&day.to_string(), |report_path| {
+                let mut child = command
+                    .env(REPORT_FILE_ENV, report_path)
+                    .stdin(Stdio::null())
+                    .stdout(output())
+                    .stderr(output())
+                    .spawn()?;
+
+                // drain the pipes while waiting, a child blocked on a full pipe never finishes.
+                let stdout = child.stdout.take().map(read_in_background);
+                let stderr = child.stderr.take().map(read_in_background);
+
+                let exit_status = wait_with_timeout(&mut child, report_path, timeout)?;
+
+                let collect = |reader: Option<JoinHandle<Vec<u8>>>| {
+                    reader
+                        .and_then(|reader| reader.join().ok())
+                        .unwrap_or_default()
+                };
 
-        let mut output = vec![];
+                Ok((exit_status, collect(stdout), collect(stderr)))
+            })?;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        Ok(ChildRun {
+            reports,
+            exit_status,
+            stdout,
+            stderr,
+        })
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    fn read_in_background(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buffer = vec![];
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
+    }
+
+    /// Wait for the child to exit. Returns `None` if it was killed after timing out.
+    fn wait_with_timeout(
+        child: &mut Child,
+        report_path: &Path,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, io::Error> {
+        let Some(timeout) = timeout else {
+            return child.wait().map(Some);
+        };
+
+        let mut deadline = Instant::now() + timeout;
+        let mut finished_parts = 0;
+
+        loop {
+            if let Some(exit_status) = child.try_wait()? {
+                return Ok(Some(exit_status));
+            }
+
+            // every line of the report file is a finished part.
+            let parts = fs::read(report_path)
+                .map(|bytes| bytes.iter().filter(|b| **b == b'\n').count())
+                .unwrap_or(0);
+
+            if parts > finished_parts {
+                finished_parts = parts;
+                deadline = Instant::now() + timeout;
+            } else if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(POLL_INTERVAL);
         }
+    }
+
+    /// Run a child with a fresh report file and read the reports it wrote.
+    fn with_report_file<T>(
+        name: &str,
+        run: impl FnOnce(&Path) -> Result<T, io::Error>,
+    ) -> Result<(Vec<PartReport>, T), Error> {
+        let report_path =
+            env::temp_dir().join(format!("aoc-report-{}-{name}.jsonl", process::id()));
+        let _ = fs::remove_file(&report_path);
+
+        let result = run(&report_path);
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let reports = report::read_file(&report_path).map_err(Error::Report);
+        let _ = fs::remove_file(&report_path);
 
-        Ok(output)
+        Ok((reports?, result?))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// The timing of a day, from the reports of its solved parts and its parse step.
+    pub fn timing_from_reports(
+        reports: &[PartReport],
+        day: Day,
+        status: DayStatus,
+    ) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            status,
+            parse: None,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| matches!(r.status, Status::Solved | Status::Parsed))
+            .for_each(|r| {
+                let timing = Some(PartTiming {
+                    nanos: r.nanos,
+                    stddev_nanos: Some(r.stddev_nanos),
+                    samples: Some(r.samples),
+                    allocations: r.allocations,
+                });
+
+                match r.part {
+                    PARSE_PART => timings.parse = timing,
+                    1 => timings.part_1 = timing,
+                    2 => timings.part_2 = timing,
+                    _ => {}
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +732,110 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_reports;
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                report::{PartReport, Status, PARSE_PART},
+                timings::DayStatus,
+            },
+        };
+
+        fn solved(part: u8, nanos: f64) -> PartReport {
+            PartReport {
+                day: day!(1),
+                part,
+                status: Status::Solved,
+                answer: Some("10".into()),
+                nanos,
+                stddev_nanos: 1.0,
+                samples: 100,
+                allocations: None,
+                error: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+        fn times_solved_parts() {
+            let res = timing_from_reports(
+                &[solved(1, 74.0), solved(2, 74_130_000.0)],
                 day!(1),
+                DayStatus::Solved,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 74130074_f64);
+            assert_eq!(res.part_1.unwrap().nanos, 74.0);
+            assert_eq!(res.part_1.unwrap().samples, Some(100));
+            assert_eq!(res.part_2.unwrap().nanos, 74_130_000.0);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
+        fn times_parse_steps() {
+            let mut parse = solved(PARSE_PART, 6.0);
+            parse.status = Status::Parsed;
+            parse.answer = None;
+            let res = timing_from_reports(
+                &[parse, solved(1, 74.0), solved(2, 20.0)],
                 day!(1),
+                DayStatus::Solved,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos(), 100_f64);
+            assert_eq!(res.parse.unwrap().nanos, 6.0);
+            assert_eq!(res.part_1.unwrap().nanos, 74.0);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+        fn times_multiline_answers() {
+            let mut report = solved(1, 2_000_000_000.0);
+            report.answer = Some("Part 2: 10s (100ms @ 1 samples)\n#..#".into());
+            let res = timing_from_reports(&[report], day!(1), DayStatus::Solved);
+            assert_approx_eq!(res.total_nanos(), 2000000000_f64);
+            assert_eq!(res.part_1.unwrap().nanos, 2_000_000_000.0);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn skips_missing_parts() {
+            let unsolved = |part| PartReport {
+                day: day!(1),
+                part,
+                status: Status::Unsolved,
+                answer: None,
+                nanos: 10.0,
+                stddev_nanos: 0.0,
+                samples: 1,
+                allocations: None,
+                error: None,
+            };
+            let res = timing_from_reports(&[unsolved(1), unsolved(2)], day!(1), DayStatus::Solved);
+            assert_approx_eq!(res.total_nanos(), 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::describe_failures;
+    use crate::day;
+    use crate::template::report::{PartReport, PARSE_PART};
+
+    #[test]
+    fn describes_failed_parts() {
+        let reports = [
+            PartReport::failed(day!(3), PARSE_PART, "bad input".into()),
+            PartReport::failed(day!(3), 2, "attempt to subtract with overflow".into()),
+        ];
+
+        assert_eq!(
+            describe_failures(&reports),
+            vec![
+                "panicked in the parse step: bad input",
+                "panicked in part 2: attempt to subtract with overflow"
+            ]
+        );
+    }
+}
@@ -1,65 +1,162 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdout, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::allocations::{self, Allocations};
+use crate::template::answers::{self, Answers, Verification};
+use crate::template::aoc_client::{AocClient, AocClientError, Submission, SubmissionOutcome};
+use crate::template::report::{self, PartReport};
+use crate::template::submissions::SubmissionLog;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{InputSource, Params, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Measurement {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, measurement) = report_failure(puzzle, part, || {
+        run_timed(func, input, |result| print_result(result, &part_str, ""))
+    });
+
+    let answer = result.as_ref().map(ToString::to_string);
+    // stored answers only apply to the puzzle input in `data/inputs`, without parameters.
+    let verification = answer
+        .as_deref()
+        .filter(|_| reads_puzzle_input())
+        .map(|answer| Answers::read_from_file(puzzle).verify(part, answer));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_measurement(&measurement),
+            format_verification(verification.as_ref())
+        ),
+    );
+
+    if let Err(e) = report::append(&PartReport::new(puzzle.day, part, answer, &measurement)) {
+        eprintln!("Failed to write part report: {e}");
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, puzzle, part);
     }
+
+    measurement
+}
+
+/// Run the shared parse step of a solution and hand its output to the parts.
+/// It is timed and reported like a part, see [`report::PARSE_PART`].
+pub fn run_parse<'a, T>(func: impl Fn(&'a str) -> T, input: &'a str, puzzle: PuzzleId) -> T {
+    let (parsed, measurement) = report_failure(puzzle, report::PARSE_PART, || {
+        run_timed(func, input, |_| print_placeholder("Parse:"))
+    });
+
+    clear_placeholder();
+    println!("Parse:{}", format_measurement(&measurement));
+
+    if let Err(e) = report::append(&PartReport::parse(puzzle.day, &measurement)) {
+        eprintln!("Failed to write part report: {e}");
+    }
+
+    parsed
+}
+
+/// Runs a part, and reports it as failed with the panic message if it panics. The panic is
+/// resumed afterwards, so the binary still exits with an error.
+fn report_failure<T>(puzzle: PuzzleId, part: u8, func: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(func)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".into());
+
+        if let Err(e) = report::append(&PartReport::failed(puzzle.day, part, message)) {
+            eprintln!("Failed to write part report: {e}");
+        }
+
+        panic::resume_unwind(payload)
+    })
+}
+
+/// Whether this run solves the puzzle itself: its input from `data/inputs`, without parameters.
+fn reads_puzzle_input() -> bool {
+    let args: Vec<String> = env::args().skip(1).collect();
+    InputSource::from_args(&args) == Ok(InputSource::Puzzle) && Params::from_args(&args).is_empty()
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        allocations::count(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let mut measurement = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Measurement::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    // benching runs the part many times, only the first run counts allocations.
+    measurement.allocations = allocations;
+
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Minimum amount of time spent running a solution before samples are recorded.
+const WARMUP_DURATION: Duration = Duration::from_millis(100);
+
+/// Samples with a modified z-score above this threshold are rejected as outliers.
+/// See: Iglewicz and Hoaglin, "How to Detect and Handle Outliers" (1993).
+const OUTLIER_THRESHOLD: f64 = 3.5;
+
+/// Scales a z-score of a normal distribution to the median absolute deviation.
+const MAD_SCALE: f64 = 0.6745;
+
+/// Two-sided z-value of the 95% confidence interval.
+pub(crate) const Z_95: f64 = 1.96;
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Measurement {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    print_placeholder(&format!(" > {ANSI_ITALIC}benching{ANSI_RESET}"));
     let _ = stdout.flush();
 
+    // the first, untimed run already warmed up caches for slow solutions.
+    let mut estimate = *base_time;
+    if estimate < WARMUP_DURATION {
+        let mut warmup_iterations = 0;
+        let timer = Instant::now();
+        while timer.elapsed() < WARMUP_DURATION {
+            black_box(func(black_box(input.clone())));
+            warmup_iterations += 1;
+        }
+        estimate = timer.elapsed() / warmup_iterations;
+    }
+
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (Duration::from_secs(1).as_nanos() / cmp::max(estimate.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,26 +168,131 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Measurement::from_samples(&timers)
+}
+
+/// Summary statistics of the samples taken while benching a solution part.
+///
+/// All statistics except `samples` and `outliers` are computed after outliers have been rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    /// Number of measured runs, including outliers.
+    pub samples: u128,
+    /// Number of runs rejected by the median absolute deviation filter.
+    pub outliers: u128,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Half-width of the 95% confidence interval of `mean`.
+    pub confidence: Duration,
+    /// Heap usage of the first run, only counted with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+impl Measurement {
+    /// Computes statistics from raw samples. Panics if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let median = percentile(&nanos, 50.0);
+
+        let mut deviations: Vec<f64> = nanos.iter().map(|x| (x - median).abs()).collect();
+        deviations.sort_unstable_by(f64::total_cmp);
+        let mad = percentile(&deviations, 50.0);
+
+        // NOTE: a MAD of zero means that most samples are identical, there is nothing to reject then.
+        let kept: Vec<f64> = if mad > 0.0 {
+            nanos
+                .iter()
+                .copied()
+                .filter(|x| MAD_SCALE * (x - median).abs() / mad <= OUTLIER_THRESHOLD)
+                .collect()
+        } else {
+            nanos.clone()
+        };
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let stddev = variance.sqrt();
+
+        Measurement {
+            samples: nanos.len() as u128,
+            outliers: (nanos.len() - kept.len()) as u128,
+            min: nanos_to_duration(kept[0]),
+            median: nanos_to_duration(percentile(&kept, 50.0)),
+            mean: nanos_to_duration(mean),
+            p95: nanos_to_duration(percentile(&kept, 95.0)),
+            stddev: nanos_to_duration(stddev),
+            confidence: nanos_to_duration(Z_95 * stddev / n.sqrt()),
+            allocations: None,
+        }
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Linearly interpolated percentile of a sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+fn format_measurement(measurement: &Measurement) -> String {
+    let Measurement {
+        samples,
+        outliers,
+        min,
+        median,
+        mean,
+        p95,
+        stddev,
+        confidence,
+        allocations,
+    } = measurement;
+
+    let allocations = allocations.map_or_else(String::new, |a| format!(" [{}]", a.summary()));
+
+    if *samples == 1 {
+        format!(" ({median:.1?}){allocations}")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({median:.1?} @ {samples} samples) [mean {mean:.1?} ± {confidence:.1?}, σ {stddev:.1?}, min {min:.1?}, p95 {p95:.1?}, {outliers} outliers]{allocations}"
+        )
+    }
+}
+
+fn format_verification(verification: Option<&Verification>) -> String {
+    match verification {
+        Some(Verification::Correct) => " ✓".into(),
+        Some(Verification::Incorrect { expected }) => format!(" ✗ (expected {expected})"),
+        Some(Verification::Unknown) | None => String::new(),
+    }
+}
+
+/// Prints a line that is overwritten once a step finished, e.g. while benching. Only on a terminal:
+/// captured or piped output would keep both the placeholder and the final line.
+fn print_placeholder(str: &str) {
+    if stdout().is_terminal() {
+        print!("{str}");
+    }
+}
+
+/// Moves back to the start of the line, so the final line overwrites the placeholder.
+fn clear_placeholder() {
+    if stdout().is_terminal() {
+        print!("\r");
     }
 }
 
@@ -102,27 +304,27 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    print_placeholder(&str);
                 } else {
-                    print!("\r");
+                    clear_placeholder();
                     println!("{str}");
                     println!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    print_placeholder(&str);
                 } else {
-                    print!("\r");
+                    clear_placeholder();
                     println!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                print_placeholder(&format!("{part}: ✖"));
             } else {
-                print!("\r");
+                clear_placeholder();
                 println!("{part}: ✖             ");
             }
         }
@@ -131,12 +333,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the answer was not rejected before and lies within known too high / too low bounds.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Submission, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +362,97 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if !reads_puzzle_input() {
+        eprintln!(
+            "Not submitting an answer for another input than data/inputs, or with parameters."
+        );
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let answer = result.to_string();
+    let mut log = SubmissionLog::read_from_file(puzzle);
+
+    if let Err(rejection) = log.check(part, &answer) {
+        eprintln!("Not submitting {answer}: {rejection}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let response = client.submit(puzzle, part, &answer);
+
+    match &response {
+        Ok(submission) => {
+            println!("{}", submission.message);
+
+            log.push(part, &answer, submission.outcome);
+            if let Err(e) = log.store_file(puzzle) {
+                eprintln!("Failed to store submission: {e}");
+            }
+
+            if submission.outcome == SubmissionOutcome::Correct {
+                match answers::store(puzzle, part, &answer) {
+                    Ok(()) => println!("Stored answer for regression checks."),
+                    Err(e) => eprintln!("Failed to store answer: {e}"),
+                }
+            }
+        }
+        Err(e) => eprintln!("failed to submit answer: {e}"),
+    }
+
+    Some(response)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Measurement;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let m = Measurement::from_samples(&nanos(&[42]));
+        assert_eq!(m.samples, 1);
+        assert_eq!(m.outliers, 0);
+        assert_eq!(m.median, Duration::from_nanos(42));
+        assert_eq!(m.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let m = Measurement::from_samples(&nanos(&[10, 12, 11, 13, 14]));
+        assert_eq!(m.samples, 5);
+        assert_eq!(m.outliers, 0);
+        assert_eq!(m.min, Duration::from_nanos(10));
+        assert_eq!(m.median, Duration::from_nanos(12));
+        assert_eq!(m.mean, Duration::from_nanos(12));
+        assert_eq!(m.p95, Duration::from_nanos(14));
+        assert_eq!(m.stddev, Duration::from_nanos(2));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let m = Measurement::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(m.samples, 8);
+        assert_eq!(m.outliers, 1);
+        assert_eq!(m.median, Duration::from_nanos(100));
+        assert_eq!(m.mean, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn keeps_identical_samples() {
+        let m = Measurement::from_samples(&nanos(&[7, 7, 7, 7, 50]));
+        assert_eq!(m.outliers, 0);
+        assert_eq!(m.median, Duration::from_nanos(7));
+    }
 }
@@ -0,0 +1,196 @@
+/// Templates of the solution modules written by `scaffold`.
+/// The built-in templates live in `src/templates` and are embedded in the binary. Custom templates
+/// are read from `templates/<name>.txt` in the repository and take precedence over built-ins with
+/// the same name, so `templates/default.txt` replaces the default template.
+use std::{fs, io, sync::OnceLock};
+
+use regex::Regex;
+
+use crate::template::root_dir;
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Type of the answers of the scaffolded parts, e.g. `u64` for puzzles with large answers.
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Directory of custom templates, relative to the root of the repository.
+pub const CUSTOM_TEMPLATE_DIR: &str = "templates";
+
+macro_rules! builtin {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    builtin!("default"),
+    builtin!("grid"),
+    builtin!("nom"),
+    builtin!("solve"),
+];
+
+/// Integer types a template can return, with the range of answers that fit them.
+const INTEGER_TYPES: [(&str, i128, i128); 12] = [
+    ("u8", 0, u8::MAX as i128),
+    ("u16", 0, u16::MAX as i128),
+    ("u32", 0, u32::MAX as i128),
+    ("u64", 0, u64::MAX as i128),
+    ("u128", 0, i128::MAX),
+    ("usize", 0, usize::MAX as i128),
+    ("i8", i8::MIN as i128, i8::MAX as i128),
+    ("i16", i16::MIN as i128, i16::MAX as i128),
+    ("i32", i32::MIN as i128, i32::MAX as i128),
+    ("i64", i64::MIN as i128, i64::MAX as i128),
+    ("i128", i128::MIN, i128::MAX),
+    ("isize", isize::MIN as i128, isize::MAX as i128),
+];
+
+/// Reads the template `name`, looking at the custom templates first.
+pub fn load(name: &str) -> Result<String, String> {
+    let custom_path = root_dir()
+        .join(CUSTOM_TEMPLATE_DIR)
+        .join(format!("{name}.txt"));
+
+    match fs::read_to_string(&custom_path) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("Failed to read \"{}\": {e}", custom_path.display())),
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            format!(
+                "Unknown template `{name}`, available templates: {}.",
+                available().join(", ")
+            )
+        })
+}
+
+/// Names of the built-in and custom templates, sorted and without duplicates.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(root_dir().join(CUSTOM_TEMPLATE_DIR)) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            path.file_stem()?.to_str().map(String::from)
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Replaces the placeholders in a template. Also returns the `%NAME%` placeholders it does not
+/// know, which are most likely typos in a custom template.
+pub fn render(template: &str, placeholders: &[(&str, String)]) -> (String, Vec<String>) {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+
+    let unknown = PLACEHOLDER
+        .get_or_init(|| Regex::new(r"%[A-Z][A-Z_]*%").unwrap())
+        .find_iter(template)
+        .map(|m| m.as_str())
+        .filter(|name| placeholders.iter().all(|(known, _)| known != name))
+        .map(String::from)
+        .collect();
+
+    let rendered = placeholders
+        .iter()
+        .fold(template.to_string(), |rendered, (name, value)| {
+            rendered.replace(name, value)
+        });
+
+    (rendered, unknown)
+}
+
+/// Rust literal of an expected answer for the answer type of the template, e.g. `Some(42)`.
+/// Returns `None` if the answer does not fit the type.
+pub fn answer_literal(answer: &str, answer_type: &str) -> Option<String> {
+    if answer_type == "String" {
+        return Some(format!("Some({answer:?}.to_string())"));
+    }
+
+    let (_, min, max) = INTEGER_TYPES.iter().find(|(ty, _, _)| *ty == answer_type)?;
+
+    answer
+        .parse::<i128>()
+        .ok()
+        .filter(|value| (*min..=*max).contains(value))
+        .map(|value| format!("Some({value})"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_literal, available, load, render, BUILTIN_TEMPLATES};
+
+    #[test]
+    fn loads_builtin_templates() {
+        for (name, _) in BUILTIN_TEMPLATES {
+            assert!(load(name).unwrap().contains("solution!(%SOLUTION_ARGS%"));
+        }
+        assert!(available().iter().any(|name| name == "grid"));
+    }
+
+    #[test]
+    fn rejects_unknown_templates() {
+        let err = load("does-not-exist").unwrap_err();
+        assert!(err.starts_with("Unknown template `does-not-exist`"));
+        assert!(err.contains("default") && err.contains("grid"));
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let (rendered, unknown) = render(
+            "// %TITLE% (%YEAR%)\nsolution!(%DAY_NUMBER%) -> %ANSWER_TYPE% %TYPO%",
+            &[
+                ("%TITLE%", "Historian Hysteria".into()),
+                ("%YEAR%", "2024".into()),
+                ("%DAY_NUMBER%", "1".into()),
+                ("%ANSWER_TYPE%", "u64".into()),
+            ],
+        );
+        assert_eq!(
+            rendered,
+            "// Historian Hysteria (2024)\nsolution!(1) -> u64 %TYPO%"
+        );
+        assert_eq!(unknown, vec!["%TYPO%".to_string()]);
+    }
+
+    #[test]
+    fn formats_answers_for_the_answer_type() {
+        assert_eq!(answer_literal("11", "u32"), Some("Some(11)".into()));
+        assert_eq!(answer_literal("-3", "u32"), None);
+        assert_eq!(answer_literal("-3", "i64"), Some("Some(-3)".into()));
+        assert_eq!(answer_literal("5000000000", "u32"), None);
+        assert_eq!(
+            answer_literal("5000000000", "u64"),
+            Some("Some(5000000000)".into())
+        );
+        assert_eq!(answer_literal("CMZ", "u32"), None);
+        assert_eq!(
+            answer_literal("CMZ", "String"),
+            Some("Some(\"CMZ\".to_string())".into())
+        );
+        assert_eq!(answer_literal("1", "Vec<u8>"), None);
+    }
+}
@@ -0,0 +1,354 @@
+/// Module that keeps a log of submitted answers, so known-wrong answers are never sent twice.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SubmissionOutcome;
+use crate::template::{data_dir, PuzzleId};
+
+static SUBMISSIONS_DIR: &str = "submissions";
+
+/// A single submitted answer and the response of the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionRecord {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// All submissions of a single day, stored as `data/submissions/{day}.json`,
+/// or `data/{year}/submissions/{day}.json` for years in their own directories.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub submissions: Vec<SubmissionRecord>,
+}
+
+/// Reason for not sending an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part was already solved with this answer.
+    AlreadyCorrect,
+    /// The answer was submitted before and was wrong.
+    AlreadyWrong(SubmissionOutcome),
+    /// The answer is at or above an answer that was too high.
+    AboveBound(String),
+    /// The answer is at or below an answer that was too low.
+    BelowBound(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyCorrect => write!(f, "this answer was already accepted."),
+            Rejection::AlreadyWrong(SubmissionOutcome::TooHigh) => {
+                write!(f, "this answer was already rejected as too high.")
+            }
+            Rejection::AlreadyWrong(SubmissionOutcome::TooLow) => {
+                write!(f, "this answer was already rejected as too low.")
+            }
+            Rejection::AlreadyWrong(_) => write!(f, "this answer was already rejected."),
+            Rejection::AboveBound(bound) => {
+                write!(f, "answer {bound} was already rejected as too high.")
+            }
+            Rejection::BelowBound(bound) => {
+                write!(f, "answer {bound} was already rejected as too low.")
+            }
+        }
+    }
+}
+
+impl SubmissionLog {
+    /// Rehydrate the log from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file(puzzle: PuzzleId) -> Self {
+        let s = match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => s,
+            Err(_) => return SubmissionLog::default(),
+        };
+
+        match SubmissionLog::try_from(s) {
+            Ok(log) => log,
+            Err(e) => {
+                eprintln!("{e}");
+                SubmissionLog::default()
+            }
+        }
+    }
+
+    /// Dehydrate the log to a JSON file.
+    pub fn store_file(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        fs::create_dir_all(data_dir(puzzle.year).join(SUBMISSIONS_DIR))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn push(&mut self, part: u8, answer: &str, outcome: SubmissionOutcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.submissions.push(SubmissionRecord {
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp,
+        });
+    }
+
+    /// Check an answer against earlier submissions before sending it.
+    /// Only responses where the website actually checked the answer are taken into account.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Rejection> {
+        let checked = self
+            .submissions
+            .iter()
+            .filter(|s| s.part == part && s.outcome.is_checked());
+
+        let mut too_high: Option<(i128, &str)> = None;
+        let mut too_low: Option<(i128, &str)> = None;
+
+        for submission in checked {
+            if submission.answer == answer {
+                return Err(match submission.outcome {
+                    SubmissionOutcome::Correct => Rejection::AlreadyCorrect,
+                    outcome => Rejection::AlreadyWrong(outcome),
+                });
+            }
+
+            let Ok(value) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match submission.outcome {
+                SubmissionOutcome::TooHigh if too_high.is_none_or(|(x, _)| value < x) => {
+                    too_high = Some((value, &submission.answer));
+                }
+                SubmissionOutcome::TooLow if too_low.is_none_or(|(x, _)| value > x) => {
+                    too_low = Some((value, &submission.answer));
+                }
+                _ => {}
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        if let Some((bound, bound_str)) = too_high {
+            if value >= bound {
+                return Err(Rejection::AboveBound(bound_str.into()));
+            }
+        }
+
+        if let Some((bound, bound_str)) = too_low {
+            if value <= bound {
+                return Err(Rejection::BelowBound(bound_str.into()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn get_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path(SUBMISSIONS_DIR, "json")
+}
+
+fn outcome_to_str(outcome: SubmissionOutcome) -> &'static str {
+    match outcome {
+        SubmissionOutcome::Correct => "correct",
+        SubmissionOutcome::TooHigh => "too_high",
+        SubmissionOutcome::TooLow => "too_low",
+        SubmissionOutcome::Incorrect => "incorrect",
+        SubmissionOutcome::Wait => "wait",
+        SubmissionOutcome::AlreadySolved => "already_solved",
+        SubmissionOutcome::Unknown => "unknown",
+    }
+}
+
+fn outcome_from_str(s: &str) -> Option<SubmissionOutcome> {
+    match s {
+        "correct" => Some(SubmissionOutcome::Correct),
+        "too_high" => Some(SubmissionOutcome::TooHigh),
+        "too_low" => Some(SubmissionOutcome::TooLow),
+        "incorrect" => Some(SubmissionOutcome::Incorrect),
+        "wait" => Some(SubmissionOutcome::Wait),
+        "already_solved" => Some(SubmissionOutcome::AlreadySolved),
+        "unknown" => Some(SubmissionOutcome::Unknown),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<SubmissionLog> for JsonValue {
+    fn from(value: SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.into_iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<SubmissionRecord> for JsonValue {
+    fn from(value: SubmissionRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_to_str(value.outcome).into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let submissions = json
+            .get("submissions")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected submissions to be an array.")?
+            .iter()
+            .map(SubmissionRecord::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(SubmissionLog { submissions })
+    }
+}
+
+impl TryFrom<&JsonValue> for SubmissionRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|p| *p == 1.0 || *p == 2.0)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|s| outcome_from_str(s))
+            .ok_or("Expected submission.outcome to be a known outcome.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(SubmissionRecord {
+            part: part as u8,
+            answer: answer.clone(),
+            outcome,
+            timestamp: timestamp as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Rejection, SubmissionLog};
+    use crate::template::aoc_client::SubmissionOutcome;
+
+    fn log(entries: &[(u8, &str, SubmissionOutcome)]) -> SubmissionLog {
+        let mut log = SubmissionLog::default();
+        for (part, answer, outcome) in entries {
+            log.push(*part, answer, *outcome);
+        }
+        log
+    }
+
+    #[test]
+    fn roundtrips_submission_log() {
+        let log = log(&[
+            (1, "42", SubmissionOutcome::TooLow),
+            (1, "50", SubmissionOutcome::Correct),
+            (2, "#..#", SubmissionOutcome::Wait),
+        ]);
+        let json = tinyjson::JsonValue::from(log.clone()).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_outcome() {
+        let json = r#"{ "submissions": [{ "part": 1, "answer": "1", "outcome": "maybe", "timestamp": 0 }] }"#;
+        SubmissionLog::try_from(json.to_string()).unwrap();
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = log(&[
+            (1, "42", SubmissionOutcome::Incorrect),
+            (2, "7", SubmissionOutcome::Correct),
+            (2, "8", SubmissionOutcome::Wait),
+        ]);
+        assert_eq!(
+            log.check(1, "42"),
+            Err(Rejection::AlreadyWrong(SubmissionOutcome::Incorrect))
+        );
+        assert_eq!(log.check(1, "43"), Ok(()));
+        assert_eq!(log.check(2, "7"), Err(Rejection::AlreadyCorrect));
+        assert_eq!(log.check(2, "8"), Ok(()));
+        assert_eq!(log.check(2, "42"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = log(&[
+            (1, "100", SubmissionOutcome::TooHigh),
+            (1, "200", SubmissionOutcome::TooHigh),
+            (1, "10", SubmissionOutcome::TooLow),
+            (1, "5", SubmissionOutcome::TooLow),
+        ]);
+        assert_eq!(
+            log.check(1, "150"),
+            Err(Rejection::AboveBound("100".into()))
+        );
+        assert_eq!(log.check(1, "-3"), Err(Rejection::BelowBound("10".into())));
+        assert_eq!(log.check(1, "7"), Err(Rejection::BelowBound("10".into())));
+        assert_eq!(log.check(1, "50"), Ok(()));
+        assert_eq!(log.check(1, "abc"), Ok(()));
+        assert_eq!(log.check(2, "150"), Ok(()));
+    }
+}
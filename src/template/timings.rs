@@ -1,17 +1,106 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap, fmt::Display, fs, io::Error, path::PathBuf, str::FromStr, time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    allocations::{self, Allocations},
+    data_dir, Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the `timings.json` schema. Files without a version are version 1, which stored
+/// the parts as preformatted strings, e.g. `"74.1ms"`, and are migrated when read.
+pub const TIMINGS_VERSION: u8 = 2;
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// How the run of the day ended. Parts that finished before a failure or timeout keep their time.
+    pub status: DayStatus,
+    /// Shared parse step, only timed for solutions that pass a `parse` function to `solution!`.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+}
+
+/// How the run of a single day ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    /// The solution exited successfully, which does not imply that every part has an answer.
+    Solved,
+    /// The solution panicked or exited with an error.
+    Failed,
+    /// The solution was killed after one of its parts ran longer than the timeout.
+    TimedOut,
+    /// There is no solution for the day yet.
+    NotScaffolded,
+}
+
+impl DayStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            DayStatus::Solved => "solved",
+            DayStatus::Failed => "failed",
+            DayStatus::TimedOut => "timed_out",
+            DayStatus::NotScaffolded => "not_scaffolded",
+        }
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for DayStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            DayStatus::Solved,
+            DayStatus::Failed,
+            DayStatus::TimedOut,
+            DayStatus::NotScaffolded,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == s)
+        .ok_or(format!("unknown day status `{s}`."))
+    }
+}
+
+/// Benchmark time of a single part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Median of the samples.
+    pub nanos: f64,
+    /// Standard deviation of the samples, unknown for timings migrated from version 1.
+    pub stddev_nanos: Option<f64>,
+    /// Number of samples, unknown for timings migrated from version 1.
+    pub samples: Option<u128>,
+    /// Heap usage, only known for parts benched with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+}
+
+impl Timing {
+    /// Sum of the benched parts and the parse step.
+    pub fn total_nanos(&self) -> f64 {
+        [self.parse, self.part_1, self.part_2]
+            .iter()
+            .flatten()
+            .map(|part| part.nanos)
+            .sum()
+    }
+}
+
+impl PartTiming {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.nanos as u64)
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,16 +111,17 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(data_dir(year))?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -58,28 +148,57 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day
+                && t.status == DayStatus::Solved
+                && t.part_1.is_some()
+                && t.part_2.is_some()
+        })
     }
 }
 
+/// Timings live in `data/timings.json`, or `data/{year}/timings.json` for years in their own directories.
+fn get_path(year: Year) -> PathBuf {
+    data_dir(year).join(TIMINGS_FILE_NAME)
+}
+
+/// Parses the `Debug` rendering of a [`Duration`] as stored by version 1, e.g. `"74.1ms"`.
+fn parse_duration_nanos(s: &str) -> Option<f64> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let scale = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(value * scale)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -95,20 +214,34 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match json.get("version") {
+            None => 1.0,
+            Some(v) => *v
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+        let data = if version == 1.0 {
+            json_data.iter().map(Timing::try_from_v1).collect()
+        } else if version == f64::from(TIMINGS_VERSION) {
+            json_data.iter().map(Timing::try_from).collect()
+        } else {
+            Err(format!(
+                "unsupported timings version {version}, expected at most {TIMINGS_VERSION}."
+            ))
+        };
+
+        Ok(Timings { data: data? })
     }
 }
 
@@ -119,26 +252,18 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
         );
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, part) in [
+            ("parse", value.parse),
+            ("part_1", value.part_1),
+            ("part_2", value.part_2),
+        ] {
+            map.insert(key.into(), part.map_or(JsonValue::Null, JsonValue::from));
+        }
 
         JsonValue::Object(map)
     }
@@ -158,26 +283,131 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        // timings stored before days had a status only contain days that ran successfully.
+        let status = match json.get("status") {
+            None => DayStatus::Solved,
+            Some(v) => v
+                .get::<String>()
+                .and_then(|s| DayStatus::from_str(s).ok())
+                .ok_or("Expected timing.status to be a day status.")?,
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part = |key: &str| match json.get(key) {
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => PartTiming::try_from(v).map(Some),
+            None => Err(format!("Expected timing.{key} to be null or an object.")),
+        };
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        // the parse step is optional, timings stored before it was timed do not have it.
+        let parse = match json.get("parse") {
+            Some(_) => part("parse")?,
+            None => None,
+        };
+
+        Ok(Timing {
+            day,
+            status,
+            parse,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+impl Timing {
+    /// Reads a timing of version 1, with parts as strings and optional `part_{n}_stats` objects.
+    fn try_from_v1(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected timing.day to be a Day struct.")?;
+
+        let part = |key: &str| {
+            let Some(stats) = json.get(&format!("{key}_stats")).filter(|v| !v.is_null()) else {
+                return match json.get(key) {
+                    Some(v) if v.is_null() => Ok(None),
+                    Some(v) => v
+                        .get::<String>()
+                        .and_then(|s| parse_duration_nanos(s))
+                        .map(|nanos| {
+                            Some(PartTiming {
+                                nanos,
+                                stddev_nanos: None,
+                                samples: None,
+                                allocations: None,
+                            })
+                        })
+                        .ok_or(format!("Expected timing.{key} to be a duration.")),
+                    None => Err(format!("Expected timing.{key} to be null or string.")),
+                };
+            };
+
+            PartTiming::try_from(stats).map(Some)
+        };
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            status: DayStatus::Solved,
+            parse: None,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<PartTiming> for JsonValue {
+    fn from(value: PartTiming) -> Self {
+        let optional = |x: Option<f64>| x.map_or(JsonValue::Null, JsonValue::Number);
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("stddev_nanos".into(), optional(value.stddev_nanos));
+        map.insert("samples".into(), optional(value.samples.map(|x| x as f64)));
+        map.insert(
+            "allocations".into(),
+            value.allocations.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.nanos to be a number.")?;
+
+        let optional = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .copied()
+                .map(Some)
+                .ok_or(format!("Expected part.{key} to be null or a number.")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartTiming {
+            nanos,
+            stddev_nanos: optional("stddev_nanos")?,
+            samples: optional("samples")?.map(|x| x as u128),
+            allocations: allocations::optional_from_json(json, "allocations")?,
         })
     }
 }
@@ -188,46 +418,113 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{DayStatus, PartTiming, Timing, Timings};
+    use crate::template::allocations::Allocations;
+
+    pub fn part(nanos: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos,
+            stddev_nanos: Some(nanos / 100.0),
+            samples: Some(100),
+            allocations: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: part(1e+10),
+                    part_2: part(2e+10),
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: part(3e+10).map(|part| PartTiming {
+                        allocations: Some(Allocations {
+                            count: 10,
+                            bytes: 2048,
+                            peak_bytes: 1024,
+                        }),
+                        ..part
+                    }),
+                    part_2: part(4e+10),
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    status: DayStatus::TimedOut,
+                    parse: None,
+                    part_1: part(4e+10),
                     part_2: None,
-                    total_nanos: 4e+10,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{DayStatus, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "stddev_nanos": 2000, "samples": 500 }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.status, DayStatus::Solved);
+            let part_1 = timing.part_1.unwrap();
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.stddev_nanos, Some(2000_f64));
+            assert_eq!(part_1.samples, Some(500));
+            assert_eq!(part_1.allocations, None);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos(), 1_000_000_f64);
+        }
+
+        #[test]
+        fn handles_day_statuses() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "status": "timed_out", "part_1": null, "part_2": null }, { "day": "02", "status": "failed", "part_1": { "nanos": 10 }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].status, DayStatus::TimedOut);
+            assert_eq!(timings.data[1].status, DayStatus::Failed);
+            assert_eq!(timings.data[1].part_1.unwrap().nanos, 10_f64);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_statuses() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "status": "crashed", "part_1": null, "part_2": null }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn migrates_string_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.1ms", "part_2": "1.5µs", "total_nanos": 74101500 }, { "day": "02", "part_1": "2.0s", "part_2": null, "total_nanos": 2000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.unwrap();
+            assert_eq!(part_1.nanos, 74_100_000_f64);
+            assert_eq!(part_1.stddev_nanos, None);
+            assert_eq!(part_1.samples, None);
+            assert_eq!(timings.data[0].part_2.unwrap().nanos, 1500_f64);
+            assert_eq!(timings.data[1].part_1.unwrap().nanos, 2e+9);
+            assert_eq!(timings.data[1].part_2, None);
+        }
+
+        #[test]
+        fn migrates_string_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "nanos": 1000000, "stddev_nanos": 2000, "samples": 500 } }] }"#.to_string();
+            let timing = Timings::try_from(json).unwrap().data.remove(0);
+            let part_1 = timing.part_1.unwrap();
+            assert_eq!(part_1.stddev_nanos, Some(2000_f64));
+            assert_eq!(part_1.samples, Some(500));
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
         #[test]
@@ -250,10 +547,18 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -261,11 +566,10 @@ mod tests {
         fn serializes_timings() {
             let timings = get_mock_timings();
             let value = JsonValue::try_from(timings).unwrap();
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
+            assert_eq!(json.get("version").unwrap().get::<f64>(), Some(&2.0));
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
+                json.get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
                     .unwrap()
@@ -273,12 +577,20 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert_eq!(Timings::try_from(json).unwrap().data, timings.data);
+        }
     }
 
     mod is_day_complete {
+        use super::part;
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{DayStatus, Timing, Timings},
         };
 
         #[test]
@@ -286,13 +598,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: part(1e+6),
+                    part_2: part(2e+6),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +613,29 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    status: DayStatus::Solved,
+                    parse: None,
+                    part_1: part(1e+6),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_failed_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    status: DayStatus::Failed,
+                    parse: None,
+                    part_1: part(1e+6),
+                    part_2: part(2e+6),
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -314,20 +643,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    status: DayStatus::Solved,
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{DayStatus, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -338,9 +668,10 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    status: DayStatus::Solved,
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
@@ -358,9 +689,10 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    status: DayStatus::Solved,
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
@@ -368,7 +700,7 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0_f64);
             assert_eq!(merged.data[2].day, day!(4));
         }
 